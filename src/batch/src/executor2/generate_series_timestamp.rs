@@ -0,0 +1,247 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chrono::{Duration, Months, NaiveDateTime};
+use futures_async_stream::try_stream;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{Array, ArrayBuilder, DataChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, RwError};
+use risingwave_common::types::{Interval, Scalar, Timestamp, Timestamptz};
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+
+use crate::executor2::{BoxedDataChunkStream, Executor2};
+
+/// Calendar-aware `cur + step`, applying `step`'s months and days before its microseconds, the
+/// same order `timestamp + interval` uses elsewhere. A timestamp type plugs in by converting to
+/// and from [`NaiveDateTime`].
+trait CheckedAddInterval: Sized {
+    fn checked_add_interval(&self, step: Interval) -> Option<Self>;
+}
+
+fn checked_add_interval_naive(naive: NaiveDateTime, step: Interval) -> Option<NaiveDateTime> {
+    let naive = if step.months() >= 0 {
+        naive.checked_add_months(Months::new(step.months() as u32))?
+    } else {
+        naive.checked_sub_months(Months::new((-step.months()) as u32))?
+    };
+    naive.checked_add_signed(Duration::days(step.days() as i64) + Duration::microseconds(step.usecs()))
+}
+
+impl CheckedAddInterval for Timestamp {
+    fn checked_add_interval(&self, step: Interval) -> Option<Self> {
+        Some(Timestamp(checked_add_interval_naive(self.0, step)?))
+    }
+}
+
+impl CheckedAddInterval for Timestamptz {
+    fn checked_add_interval(&self, step: Interval) -> Option<Self> {
+        let naive = checked_add_interval_naive(self.to_datetime_utc().naive_utc(), step)?;
+        Some(Timestamptz::from_micros(naive.and_utc().timestamp_micros()))
+    }
+}
+
+/// Which way `step` actually moves the series, judged by applying it once to `start` and
+/// comparing the result -- not by the sign of `step`'s first non-zero component, which a
+/// mixed-sign interval (e.g. `1 mon - 40 days`) can get backwards: calendar arithmetic applies
+/// months before days, but the two can partially or fully cancel once days are applied, so the
+/// component order doesn't determine the net direction. `None` if `step` overflows applied to
+/// `start`, or if it has zero net effect, either of which would never make progress and must be
+/// rejected rather than looping forever.
+fn step_direction<T: PartialOrd + CheckedAddInterval>(start: &T, step: Interval) -> Option<bool> {
+    let next = start.checked_add_interval(step)?;
+    if next > *start {
+        Some(true)
+    } else if next < *start {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// `generate_series` over a `Timestamp`/`Timestamptz` range stepped by an `Interval`, e.g.
+/// `generate_series('2024-01-01'::timestamp, '2024-02-01', interval '1 day')`. Mirrors
+/// [`GenerateSeriesI32Executor2`](super::generate_i32_series::GenerateSeriesI32Executor2)'s
+/// chunked loop, but steps via calendar-aware [`CheckedAddInterval`] instead of `AddAssign`,
+/// since months and days don't have a fixed duration.
+pub struct GenerateSeriesTimestampExecutor2<T: Array> {
+    start: T::OwnedItem,
+    stop: T::OwnedItem,
+    step: Interval,
+
+    schema: Schema,
+    identity: String,
+}
+
+impl<T: Array> GenerateSeriesTimestampExecutor2<T> {
+    pub fn new(
+        start: T::OwnedItem,
+        stop: T::OwnedItem,
+        step: Interval,
+        schema: Schema,
+        identity: String,
+    ) -> Self {
+        Self {
+            start,
+            stop,
+            step,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl<T: Array> Executor2 for GenerateSeriesTimestampExecutor2<T>
+where
+    T::OwnedItem: PartialOrd + CheckedAddInterval,
+{
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl<T> GenerateSeriesTimestampExecutor2<T>
+where
+    T: Array,
+    T::OwnedItem: PartialOrd + CheckedAddInterval,
+{
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let Self {
+            start, stop, step, ..
+        } = *self;
+
+        let ascending = step_direction(&start, step).ok_or_else(|| {
+            RwError::from(ErrorCode::InternalError(
+                "generate_series step interval must not be zero".to_owned(),
+            ))
+        })?;
+        let in_range = |cur: &T::OwnedItem, stop: &T::OwnedItem| {
+            if ascending {
+                *cur <= *stop
+            } else {
+                *cur >= *stop
+            }
+        };
+
+        let mut cur = start;
+
+        // Simulate a do-while loop.
+        while in_range(&cur, &stop) {
+            let chunk_size = DEFAULT_CHUNK_BUFFER_SIZE;
+            let mut builder = T::Builder::new(chunk_size)?;
+
+            for _ in 0..chunk_size {
+                if !in_range(&cur, &stop) {
+                    break;
+                }
+                builder.append(Some(cur.as_scalar_ref())).unwrap();
+                cur = cur.checked_add_interval(step).ok_or_else(|| {
+                    RwError::from(ErrorCode::InternalError(
+                        "generate_series step interval overflowed the timestamp range".to_owned(),
+                    ))
+                })?;
+            }
+
+            let arr = builder.finish()?;
+            let columns = vec![Column::new(Arc::new(arr.into()))];
+            let chunk: DataChunk = DataChunk::builder().columns(columns).build();
+
+            yield chunk;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::{ArrayImpl, TimestampArray};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::try_match_expand;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generate_series_timestamp_ascending() {
+        let start: Timestamp = "2024-01-01 00:00:00".parse().unwrap();
+        let stop: Timestamp = "2024-01-04 00:00:00".parse().unwrap();
+        let step = Interval::from_month_day_usec(0, 1, 0);
+
+        let executor = Box::new(GenerateSeriesTimestampExecutor2::<TimestampArray>::new(
+            start,
+            stop,
+            step,
+            Schema::new(vec![Field::unnamed(DataType::Timestamp)]),
+            "GenerateSeriesTimestampExecutor2".to_owned(),
+        ));
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        let col = chunk.column_at(0);
+        let arr = try_match_expand!(col.array_ref(), ArrayImpl::Timestamp).unwrap();
+        assert_eq!(arr.len(), 4);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_generate_series_timestamp_rejects_zero_step() {
+        let start: Timestamp = "2024-01-01 00:00:00".parse().unwrap();
+        let stop: Timestamp = "2024-01-04 00:00:00".parse().unwrap();
+        let step = Interval::from_month_day_usec(0, 0, 0);
+
+        let executor = Box::new(GenerateSeriesTimestampExecutor2::<TimestampArray>::new(
+            start,
+            stop,
+            step,
+            Schema::new(vec![Field::unnamed(DataType::Timestamp)]),
+            "GenerateSeriesTimestampExecutor2".to_owned(),
+        ));
+        let mut stream = executor.execute();
+        assert!(stream.next().await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_series_timestamp_net_direction_overrides_first_component() {
+        // `1 month - 40 days` has a positive first (months) component, but nets to a negative
+        // movement once the days are applied from a 31-day starting month -- the series must
+        // step backward, not forward as the first component alone would suggest.
+        let start: Timestamp = "2024-01-01 00:00:00".parse().unwrap();
+        let stop: Timestamp = "2023-11-01 00:00:00".parse().unwrap();
+        let step = Interval::from_month_day_usec(1, -40, 0);
+
+        let executor = Box::new(GenerateSeriesTimestampExecutor2::<TimestampArray>::new(
+            start,
+            stop,
+            step,
+            Schema::new(vec![Field::unnamed(DataType::Timestamp)]),
+            "GenerateSeriesTimestampExecutor2".to_owned(),
+        ));
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        let col = chunk.column_at(0);
+        let arr = try_match_expand!(col.array_ref(), ArrayImpl::Timestamp).unwrap();
+        assert!(arr.len() >= 2);
+        assert!(arr.value_at(0).unwrap() > arr.value_at(1).unwrap());
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Source-like executors that produce a stream of `DataChunk`s with no upstream input, e.g.
+//! `generate_series`'s table functions.
+
+mod generate_i32_series;
+mod generate_series_timestamp;
+
+pub use generate_i32_series::GenerateSeriesI32Executor2;
+pub use generate_series_timestamp::GenerateSeriesTimestampExecutor2;
+
+use futures::stream::BoxStream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::RwError;
+
+/// The `DataChunk` stream an [`Executor2`] produces, boxed so different implementations can be
+/// returned from the same trait method.
+pub type BoxedDataChunkStream = BoxStream<'static, Result<DataChunk, RwError>>;
+
+/// An executor that produces a stream of `DataChunk`s with no upstream input.
+pub trait Executor2: Send {
+    /// The schema of the `DataChunk`s this executor yields.
+    fn schema(&self) -> &Schema;
+
+    /// A human-readable identifier, used in plan/executor debug output.
+    fn identity(&self) -> &str;
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream;
+}
@@ -0,0 +1,137 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::fifo_cache::CacheKey;
+
+/// Tracks keys recently evicted from the small (probationary) queue without paying to keep their
+/// values around.
+///
+/// This is the missing half of S3-FIFO's admission path: an item that is re-requested while its
+/// key is still in the ghost queue is judged "frequent enough" and is promoted straight into the
+/// main queue on insert, instead of being re-admitted into the small queue and risking eviction
+/// again before it can prove itself. Keys age out of the ghost queue in FIFO order once
+/// `capacity` is exceeded, same as the small/main queues they shadow.
+pub struct GhostQueue<K: CacheKey> {
+    queue: VecDeque<K>,
+    members: HashMap<K, ()>,
+    capacity: usize,
+    /// Mirrors `queue.len()` in an `AtomicUsize` so metrics can read it without taking whatever
+    /// lock guards the queue itself.
+    size: AtomicUsize,
+    /// Count of [`Self::contains`] calls that found their key present, i.e. admissions the small
+    /// queue promoted straight to the main queue instead of re-admitting.
+    hits: AtomicUsize,
+}
+
+impl<K: CacheKey> GhostQueue<K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            members: HashMap::new(),
+            capacity,
+            size: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// Records that `key` was just evicted from the small queue.
+    pub fn record_evicted(&mut self, key: K) {
+        if self.members.contains_key(&key) {
+            return;
+        }
+        if self.queue.len() >= self.capacity {
+            if let Some(oldest) = self.queue.pop_front() {
+                self.members.remove(&oldest);
+                self.size.fetch_sub(1, Ordering::Release);
+            }
+        }
+        self.members.insert(key.clone(), ());
+        self.queue.push_back(key);
+        self.size.fetch_add(1, Ordering::Release);
+    }
+
+    /// Returns whether `key` is present in the ghost queue, i.e. whether it was evicted recently
+    /// enough to be considered "frequent" on re-access. Does not remove the entry: the ghost queue
+    /// only forgets keys by aging them out via [`Self::record_evicted`]'s FIFO eviction.
+    pub fn contains(&self, key: &K) -> bool {
+        let hit = self.members.contains_key(key);
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        hit
+    }
+
+    /// Removes `key` from the ghost queue, e.g. once it has been promoted into the main queue and
+    /// should no longer count as a ghost hit.
+    pub fn remove(&mut self, key: &K) {
+        if self.members.remove(key).is_some() {
+            self.queue.retain(|k| k != key);
+            self.size.fetch_sub(1, Ordering::Release);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Atomic counterpart of [`Self::len`], safe to read from a metrics collector without
+    /// synchronizing with mutators.
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::Acquire)
+    }
+
+    /// Total number of [`Self::contains`] hits observed so far.
+    pub fn hit_count(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.members.clear();
+        self.size.store(0, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ghost_queue_tracks_recent_evictions() {
+        let mut ghost = GhostQueue::<u64>::new(2);
+        assert!(!ghost.contains(&1));
+
+        ghost.record_evicted(1);
+        ghost.record_evicted(2);
+        assert!(ghost.contains(&1));
+        assert!(ghost.contains(&2));
+
+        // Capacity is 2, so the oldest entry (1) ages out.
+        ghost.record_evicted(3);
+        assert!(!ghost.contains(&1));
+        assert!(ghost.contains(&2));
+        assert!(ghost.contains(&3));
+
+        ghost.remove(&2);
+        assert!(!ghost.contains(&2));
+        assert_eq!(ghost.len(), 1);
+    }
+}
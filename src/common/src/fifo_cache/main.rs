@@ -0,0 +1,103 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::fifo_cache::{CacheItem, CacheKey, CacheValue};
+
+/// The main (protected) FIFO queue of S3-FIFO, holding items that have proven themselves -- by
+/// surviving an access while resident in the small queue, or by hitting the ghost queue on
+/// re-insertion.
+///
+/// Eviction gives each item a second chance before dropping it: unlike the small queue, which
+/// ghosts anything it evicts, the main queue re-queues an item at the back (after decaying its
+/// frequency) rather than dropping it outright, so an item only actually leaves the cache once
+/// it's gone a full lap without being touched.
+pub struct MainCache<K: CacheKey, V: CacheValue> {
+    queue: VecDeque<Box<CacheItem<K, V>>>,
+    cost: Arc<AtomicUsize>,
+    capacity: usize,
+}
+
+impl<K: CacheKey, V: CacheValue> MainCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            cost: Arc::new(AtomicUsize::new(0)),
+            capacity,
+        }
+    }
+
+    pub fn get_size_counter(&self) -> Arc<AtomicUsize> {
+        self.cost.clone()
+    }
+
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        self.cost.load(Ordering::Acquire)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size() > self.capacity
+    }
+
+    pub fn count(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Admits `item` into the main queue.
+    pub fn insert(&mut self, mut item: Box<CacheItem<K, V>>) {
+        item.mark_main();
+        self.cost.fetch_add(item.cost(), Ordering::Release);
+        self.queue.push_back(item);
+    }
+
+    /// Evicts the first item that has gone a full lap through the queue without being touched,
+    /// giving every touched item it passes over one fewer remaining second chance.
+    ///
+    /// Since [`CacheItem::freq`] only ever decreases here (nothing re-touches items mid-scan) and
+    /// is capped, this always terminates: every requeued item's frequency strictly drops, so the
+    /// loop runs at most `capped frequency * queue length` iterations before finding a victim.
+    pub fn evict(&mut self) -> Option<Box<CacheItem<K, V>>> {
+        loop {
+            let mut item = self.queue.pop_front()?;
+            if item.freq() > 0 {
+                item.decay();
+                self.queue.push_back(item);
+                continue;
+            }
+            self.cost.fetch_sub(item.cost(), Ordering::Release);
+            item.unmark();
+            return Some(item);
+        }
+    }
+
+    /// Looks up `key` among resident items, touching it (recording an access) on a hit so
+    /// [`Self::evict`] gives it another lap instead of evicting it on the next pass.
+    ///
+    /// Linear in [`Self::count`]: same tradeoff as [`super::small::SmallHotCache::get`].
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.queue.iter().find(|item| item.key() == key).map(|item| {
+            item.touch();
+            item.value()
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.cost.store(0, Ordering::Release);
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simplified [S3-FIFO](https://s3fifo.com/) cache: new items are admitted into a small
+//! (probationary) [`SmallHotCache`] queue; items that are accessed again while still resident
+//! there are promoted into a [`MainCache`] queue with its own second-chance eviction, while
+//! untouched items are dropped and remembered only by key in a [`GhostQueue`], so a near-future
+//! re-insertion of the same key is recognized as "actually frequent" and promoted straight to the
+//! main queue instead of having to survive the small queue all over again.
+//!
+//! [`Cache`] is the top-level handle driving all three queues together; [`SmallHotCache`],
+//! [`MainCache`] and [`GhostQueue`] are the isolated queues it orchestrates and are not meant to
+//! be driven directly outside of this module.
+
+mod ghost;
+mod main;
+mod small;
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub use ghost::GhostQueue;
+pub use main::MainCache;
+pub use small::{AdmissionDecision, EvictionOutcome, SmallHotCache};
+
+/// The top-level S3-FIFO cache: holds the small and main queues together and drives every
+/// cross-queue handoff [`SmallHotCache`] and [`MainCache`] can only report, not act on
+/// themselves -- a ghost-queue hit on insert, or a small-queue eviction that earned a promotion.
+pub struct Cache<K: CacheKey, V: CacheValue> {
+    small: SmallHotCache<K, V>,
+    main: MainCache<K, V>,
+}
+
+impl<K: CacheKey, V: CacheValue> Cache<K, V> {
+    /// `small_capacity` and `main_capacity` are independent cost budgets, matching S3-FIFO's
+    /// usual split of a small fraction of total capacity for the probationary queue and the rest
+    /// for the main queue.
+    pub fn new(small_capacity: usize, main_capacity: usize) -> Self {
+        Self {
+            small: SmallHotCache::new(small_capacity),
+            main: MainCache::new(main_capacity),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.small.get(key).or_else(|| self.main.get(key))
+    }
+
+    /// Admits `key` via the small queue -- or straight to the main queue, if its key is still in
+    /// the ghost queue from a recent eviction -- then evicts down to capacity, routing any
+    /// small-queue eviction that earned a promotion into the main queue instead of dropping it.
+    pub fn insert(&mut self, key: K, value: V, cost: usize) {
+        let item = Box::new(CacheItem::new(key, value, cost));
+        match self.small.insert(item) {
+            AdmissionDecision::AdmittedToSmall => {}
+            AdmissionDecision::PromoteToMain(item) => self.main.insert(item),
+        }
+        self.evict_to_capacity();
+    }
+
+    fn evict_to_capacity(&mut self) {
+        while self.small.is_full() {
+            let Some(outcome) = self.small.evict() else {
+                break;
+            };
+            if let EvictionOutcome::PromoteToMain(item) = outcome {
+                self.main.insert(item);
+            }
+        }
+        while self.main.is_full() {
+            if self.main.evict().is_none() {
+                break;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.small.count() + self.main.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// S3-FIFO's reference implementation caps access frequency at 3 so one hot item can't claim an
+/// unbounded number of second chances in [`MainCache::evict`] at the expense of everything behind
+/// it.
+const MAX_FREQ: u8 = 3;
+
+pub trait CacheKey: std::hash::Hash + Eq + Clone + Send + Sync + 'static {}
+impl<T: std::hash::Hash + Eq + Clone + Send + Sync + 'static> CacheKey for T {}
+
+pub trait CacheValue: Send + Sync + 'static {}
+impl<T: Send + Sync + 'static> CacheValue for T {}
+
+/// One entry in the cache, carrying its access frequency since last entering a queue -- the
+/// signal [`SmallHotCache::evict`] and [`MainCache::evict`] use to decide whether an evicted item
+/// deserves a second chance instead of being dropped.
+pub struct CacheItem<K: CacheKey, V: CacheValue> {
+    key: K,
+    value: V,
+    cost: usize,
+    freq: AtomicU8,
+}
+
+impl<K: CacheKey, V: CacheValue> CacheItem<K, V> {
+    pub fn new(key: K, value: V, cost: usize) -> Self {
+        Self {
+            key,
+            value,
+            cost,
+            freq: AtomicU8::new(0),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    /// Records an access, capping at [`MAX_FREQ`].
+    pub fn touch(&self) {
+        let _ = self
+            .freq
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |f| {
+                if f >= MAX_FREQ { None } else { Some(f + 1) }
+            });
+    }
+
+    pub fn freq(&self) -> u8 {
+        self.freq.load(Ordering::Acquire)
+    }
+
+    /// Takes one of an item's remaining second chances in [`MainCache::evict`]'s eviction scan:
+    /// decrements its frequency by one, never below zero.
+    pub fn decay(&self) {
+        let _ = self
+            .freq
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |f| {
+                if f == 0 { None } else { Some(f - 1) }
+            });
+    }
+
+    /// Called when the item is (re-)admitted into the small queue: clears any frequency left
+    /// over from a previous residency so it has to prove itself again.
+    pub fn mark_small(&mut self) {
+        *self.freq.get_mut() = 0;
+    }
+
+    /// Called when the item is admitted into the main queue, whether via direct promotion or a
+    /// ghost-queue hit. Unlike [`Self::mark_small`], the incoming frequency is preserved: it's
+    /// exactly the signal that earned the item its promotion.
+    pub fn mark_main(&mut self) {}
+
+    /// Called on eviction from whichever queue currently holds the item.
+    pub fn unmark(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_promotes_on_second_access_then_evicts() {
+        let mut cache = Cache::<u64, &'static str>::new(1, 1);
+
+        cache.insert(1, "a", 1);
+        assert_eq!(cache.get(&1), Some(&"a"));
+
+        // Evicted from the small queue while holding a non-zero frequency: must land in the main
+        // queue instead of being dropped.
+        cache.insert(2, "b", 1);
+        assert_eq!(cache.get(&1), Some(&"a"));
+        assert_eq!(cache.get(&2), Some(&"b"));
+    }
+
+    #[test]
+    fn test_cache_ghost_hit_promotes_straight_to_main() {
+        let mut cache = Cache::<u64, &'static str>::new(1, 1);
+
+        cache.insert(1, "a", 1);
+        // Never touched, so it's ghosted (not promoted) on eviction.
+        cache.insert(2, "b", 1);
+        assert_eq!(cache.get(&1), None);
+
+        // Re-inserting the ghosted key is judged frequent and goes straight to the main queue.
+        cache.insert(1, "a-again", 1);
+        assert_eq!(cache.get(&1), Some(&"a-again"));
+    }
+}
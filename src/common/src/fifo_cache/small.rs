@@ -16,12 +16,37 @@ use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+use crate::fifo_cache::ghost::GhostQueue;
 use crate::fifo_cache::{CacheItem, CacheKey, CacheValue};
 
+/// The small (probationary) FIFO queue of S3-FIFO, fronted by a [`GhostQueue`] of recently
+/// evicted keys.
+///
+/// On insertion, if the incoming key is still present in the ghost queue -- i.e. it was evicted
+/// from this same small queue recently enough to still be remembered -- it is judged "frequent"
+/// and should go straight to the main queue instead of being re-admitted here, where it would
+/// otherwise have to survive eviction all over again before being promoted. [`Self::insert`]
+/// reports this via its return value so callers (the top-level S3-FIFO cache) can route the item
+/// accordingly.
 pub struct SmallHotCache<K: CacheKey, V: CacheValue> {
     queue: VecDeque<Box<CacheItem<K, V>>>,
     cost: Arc<AtomicUsize>,
     capacity: usize,
+    ghost: GhostQueue<K>,
+}
+
+/// Where an inserted item ended up: newly admitted into the small queue, or judged frequent
+/// enough (a ghost-queue hit) to be promoted straight to the main queue.
+pub enum AdmissionDecision<K: CacheKey, V: CacheValue> {
+    AdmittedToSmall,
+    PromoteToMain(Box<CacheItem<K, V>>),
+}
+
+/// Where an evicted item ended up: dropped from the cache (remembered only by key, in the ghost
+/// queue), or promoted to the main queue because it proved itself with an access while resident.
+pub enum EvictionOutcome<K: CacheKey, V: CacheValue> {
+    Evicted,
+    PromoteToMain(Box<CacheItem<K, V>>),
 }
 
 impl<K: CacheKey, V: CacheValue> SmallHotCache<K, V> {
@@ -30,6 +55,7 @@ impl<K: CacheKey, V: CacheValue> SmallHotCache<K, V> {
             queue: VecDeque::new(),
             cost: Arc::new(AtomicUsize::new(0)),
             capacity,
+            ghost: GhostQueue::new(capacity),
         }
     }
 
@@ -50,23 +76,56 @@ impl<K: CacheKey, V: CacheValue> SmallHotCache<K, V> {
         self.queue.len()
     }
 
-    pub fn evict(&mut self) -> Option<Box<CacheItem<K, V>>> {
+    /// Evicts the oldest item. An item that was accessed again while sitting in the small queue
+    /// (i.e. its access frequency is non-zero) has proven itself and is promoted straight to the
+    /// main queue instead of being dropped; only an untouched item is ghosted, remembered by key
+    /// alone so a future re-insert of the same key can promote it on arrival instead.
+    pub fn evict(&mut self) -> Option<EvictionOutcome<K, V>> {
         let mut item = self.queue.pop_front()?;
         self.cost
             .fetch_sub(item.cost(), std::sync::atomic::Ordering::Release);
+
+        if item.freq() > 0 {
+            item.unmark();
+            return Some(EvictionOutcome::PromoteToMain(item));
+        }
+
         item.unmark();
-        Some(item)
+        self.ghost.record_evicted(item.key().clone());
+        Some(EvictionOutcome::Evicted)
     }
 
-    pub fn insert(&mut self, mut item: Box<CacheItem<K, V>>) {
+    /// Admits `item` into the small queue, unless its key is present in the ghost queue, in which
+    /// case it is judged frequent and handed back for the caller to insert into the main queue
+    /// instead.
+    pub fn insert(&mut self, mut item: Box<CacheItem<K, V>>) -> AdmissionDecision<K, V> {
+        if self.ghost.contains(item.key()) {
+            self.ghost.remove(item.key());
+            return AdmissionDecision::PromoteToMain(item);
+        }
+
         item.mark_small();
         self.cost
             .fetch_add(item.cost(), std::sync::atomic::Ordering::Release);
         self.queue.push_back(item);
+        AdmissionDecision::AdmittedToSmall
+    }
+
+    /// Looks up `key` among resident items, touching it (recording an access) on a hit so a
+    /// subsequent [`Self::evict`] promotes it to the main queue instead of ghosting it.
+    ///
+    /// Linear in [`Self::count`]: this queue is meant to stay small by design, so a scan here
+    /// costs about as much as the FIFO bookkeeping [`Self::evict`] already does per item.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.queue.iter().find(|item| item.key() == key).map(|item| {
+            item.touch();
+            item.value()
+        })
     }
 
     pub fn clear(&mut self) {
         self.queue.clear();
         self.cost.store(0, Ordering::Release);
+        self.ghost.clear();
     }
 }
\ No newline at end of file
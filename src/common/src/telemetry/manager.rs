@@ -0,0 +1,119 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::report::{TelemetryInfoFetcher, TelemetryReportCreator};
+use super::wal::TelemetryReportQueue;
+use super::{current_timestamp, TELEMETRY_REPORT_INTERVAL, TELEMETRY_REPORT_URL};
+
+/// Drives a node's periodic telemetry report: on every tick, fetches the node's `tracking_id`,
+/// asks `C` to build this node type's report, and routes it through a [`TelemetryReportQueue`] so
+/// a send failure is retried from durable storage rather than silently dropped.
+///
+/// The `enabled_rx` watch channel is re-read by the reporting loop on every tick (not just at
+/// startup), since telemetry can be toggled off at runtime through a system param change without
+/// restarting the node.
+pub struct TelemetryManager<F, C> {
+    info_fetcher: Arc<F>,
+    report_creator: Arc<C>,
+    enabled_rx: watch::Receiver<bool>,
+    session_id: String,
+}
+
+impl<F, C> TelemetryManager<F, C>
+where
+    F: TelemetryInfoFetcher + Send + Sync + 'static,
+    C: TelemetryReportCreator + Send + Sync + 'static,
+{
+    pub fn new(enabled_rx: watch::Receiver<bool>, info_fetcher: Arc<F>, report_creator: Arc<C>) -> Self {
+        Self {
+            info_fetcher,
+            report_creator,
+            enabled_rx,
+            session_id: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Spawns the periodic reporting loop, sending one batched report per
+    /// [`TELEMETRY_REPORT_INTERVAL`] tick through a fresh [`TelemetryReportQueue`] opened for this
+    /// node's `TelemetryNodeType`. Returns immediately; the loop runs for the life of the process.
+    pub async fn start_telemetry_reporting(&self) -> JoinHandle<()> {
+        let info_fetcher = self.info_fetcher.clone();
+        let report_creator = self.report_creator.clone();
+        let session_id = self.session_id.clone();
+        let mut enabled_rx = self.enabled_rx.clone();
+        let node_type = self.report_creator.node_type();
+        let start_time = current_timestamp();
+
+        tokio::spawn(async move {
+            let data_dir = std::env::temp_dir().join("risingwave_telemetry");
+            let mut queue = match TelemetryReportQueue::open(&data_dir, node_type) {
+                Ok(queue) => queue,
+                Err(e) => {
+                    tracing::warn!("failed to open telemetry wal, reporting disabled: {e}");
+                    return;
+                }
+            };
+
+            let mut ticker = tokio::time::interval(Duration::from_secs(TELEMETRY_REPORT_INTERVAL));
+            loop {
+                ticker.tick().await;
+                if !*enabled_rx.borrow_and_update() {
+                    continue;
+                }
+
+                let tracking_id = match info_fetcher.fetch_telemetry_info().await {
+                    Ok(Some(id)) => id,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::warn!("failed to fetch telemetry tracking id: {e}");
+                        continue;
+                    }
+                };
+                let up_time = current_timestamp().saturating_sub(start_time);
+
+                let report = match report_creator
+                    .create_report(tracking_id, session_id.clone(), up_time)
+                    .await
+                {
+                    Ok(report) => report,
+                    Err(e) => {
+                        tracing::warn!("failed to create telemetry report: {e}");
+                        continue;
+                    }
+                };
+
+                if let Err(e) = queue.report(report, TELEMETRY_REPORT_URL).await {
+                    tracing::warn!("failed to report telemetry, will retry: {e}");
+                }
+            }
+        })
+    }
+
+    /// Logs every runtime change of the telemetry enabled param. It does not control the reporting
+    /// loop, which reads the same watch channel itself on each tick.
+    pub fn watch_params_change(&self) -> JoinHandle<()> {
+        let mut enabled_rx = self.enabled_rx.clone();
+        tokio::spawn(async move {
+            while enabled_rx.changed().await.is_ok() {
+                tracing::info!("telemetry enabled param changed to {}", *enabled_rx.borrow());
+            }
+        })
+    }
+}
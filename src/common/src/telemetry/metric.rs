@@ -0,0 +1,117 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Live Prometheus gauges for the same host resource numbers [`super::SystemData`] snapshots for
+//! the (optional, 6-hourly) telemetry report, so that operators who run with
+//! `ENABLE_TELEMETRY=false` still get local resource observability through the node's existing
+//! `/metrics` endpoint.
+
+use std::time::Duration;
+
+use prometheus::core::{AtomicF64, GenericGauge};
+use prometheus::Registry;
+
+use crate::util::resource_util::cpu::total_cpu_available;
+use crate::util::resource_util::memory::{system_memory_available_bytes, total_memory_used_bytes};
+
+type Gauge = GenericGauge<AtomicF64>;
+
+/// Update interval for the live gauges. Much shorter than `TELEMETRY_REPORT_INTERVAL`, since this
+/// feeds a scrape endpoint rather than a periodic outbound report.
+pub const SYSTEM_METRICS_OBSERVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The single source of host resource numbers consumed by both the telemetry report
+/// (`SystemData::new()`) and these live gauges, so the two can never disagree.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemMetricsSnapshot {
+    pub memory_used_bytes: usize,
+    pub memory_total_bytes: usize,
+    pub cpu_available: f32,
+}
+
+impl SystemMetricsSnapshot {
+    pub fn collect() -> Self {
+        let memory_total_bytes = system_memory_available_bytes();
+        let memory_used_bytes = total_memory_used_bytes();
+        let cpu_available = total_cpu_available();
+        Self {
+            memory_used_bytes,
+            memory_total_bytes,
+            cpu_available,
+        }
+    }
+}
+
+/// Registers `rw_system_memory_used_bytes`, `rw_system_memory_total_bytes` and
+/// `rw_system_cpu_available` with `registry`, and spawns a background task that refreshes them
+/// from [`SystemMetricsSnapshot::collect`] every [`SYSTEM_METRICS_OBSERVE_INTERVAL`].
+pub struct SystemMetrics {
+    memory_used_bytes: Gauge,
+    memory_total_bytes: Gauge,
+    cpu_available: Gauge,
+}
+
+impl SystemMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        let memory_used_bytes = Gauge::new(
+            "rw_system_memory_used_bytes",
+            "Memory currently used on the host, in bytes",
+        )
+        .unwrap();
+        let memory_total_bytes = Gauge::new(
+            "rw_system_memory_total_bytes",
+            "Memory available on the host, in bytes",
+        )
+        .unwrap();
+        let cpu_available = Gauge::new(
+            "rw_system_cpu_available",
+            "Number of CPUs available to the node",
+        )
+        .unwrap();
+
+        registry
+            .register(Box::new(memory_used_bytes.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(memory_total_bytes.clone()))
+            .unwrap();
+        registry.register(Box::new(cpu_available.clone())).unwrap();
+
+        Self {
+            memory_used_bytes,
+            memory_total_bytes,
+            cpu_available,
+        }
+    }
+
+    /// Refreshes all three gauges from a fresh [`SystemMetricsSnapshot`].
+    pub fn observe(&self) {
+        let snapshot = SystemMetricsSnapshot::collect();
+        self.memory_used_bytes.set(snapshot.memory_used_bytes as f64);
+        self.memory_total_bytes
+            .set(snapshot.memory_total_bytes as f64);
+        self.cpu_available.set(snapshot.cpu_available as f64);
+    }
+
+    /// Spawns the periodic `observe()` loop on the current Tokio runtime.
+    pub fn start_observing(self: std::sync::Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SYSTEM_METRICS_OBSERVE_INTERVAL);
+            loop {
+                ticker.tick().await;
+                self.observe();
+            }
+        })
+    }
+}
@@ -13,8 +13,10 @@
 // limitations under the License.
 
 pub mod manager;
+pub mod metric;
 pub mod pb_compatible;
 pub mod report;
+pub mod wal;
 
 use std::env;
 use std::time::SystemTime;
@@ -23,9 +25,8 @@ use serde::{Deserialize, Serialize};
 use sysinfo::System;
 use thiserror_ext::AsReport;
 
+use crate::telemetry::metric::SystemMetricsSnapshot;
 use crate::util::env_var::env_var_is_true_or;
-use crate::util::resource_util::cpu::total_cpu_available;
-use crate::util::resource_util::memory::{system_memory_available_bytes, total_memory_used_bytes};
 use crate::RW_VERSION;
 
 pub const TELEMETRY_CLUSTER_TYPE: &str = "RW_TELEMETRY_TYPE";
@@ -105,10 +106,17 @@ struct Cpu {
 
 impl SystemData {
     pub fn new() -> Self {
-        let memory = {
-            let total = system_memory_available_bytes();
-            let used = total_memory_used_bytes();
-            Memory { used, total }
+        // Shared with the live `rw_system_*` gauges in `telemetry::metric`, so the periodic
+        // report and the Prometheus endpoint are always derived from the same source.
+        let SystemMetricsSnapshot {
+            memory_used_bytes,
+            memory_total_bytes,
+            cpu_available,
+        } = SystemMetricsSnapshot::collect();
+
+        let memory = Memory {
+            used: memory_used_bytes,
+            total: memory_total_bytes,
         };
 
         let os = Os {
@@ -118,7 +126,7 @@ impl SystemData {
         };
 
         let cpu = Cpu {
-            available: total_cpu_available(),
+            available: cpu_available,
         };
 
         SystemData { memory, os, cpu }
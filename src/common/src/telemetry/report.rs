@@ -0,0 +1,38 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{TelemetryNodeType, TelemetryReportBase, TelemetryResult};
+
+/// A node's source of its stable `tracking_id`, e.g. the meta client RPC that reads it from etcd.
+/// `None` means the backend hasn't assigned one yet (e.g. still bootstrapping), in which case the
+/// caller should skip reporting for that tick rather than report under a missing id.
+#[async_trait::async_trait]
+pub trait TelemetryInfoFetcher {
+    async fn fetch_telemetry_info(&self) -> TelemetryResult<Option<String>>;
+}
+
+/// Builds the node-type-specific portion of a telemetry report. Each node binary (meta, compute,
+/// frontend, compactor) implements this once, supplying whatever fields distinguish its report
+/// from the common [`TelemetryReportBase`].
+#[async_trait::async_trait]
+pub trait TelemetryReportCreator {
+    async fn create_report(
+        &self,
+        tracking_id: String,
+        session_id: String,
+        up_time: u64,
+    ) -> TelemetryResult<TelemetryReportBase>;
+
+    fn node_type(&self) -> TelemetryNodeType;
+}
@@ -0,0 +1,289 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A durable, append-only queue of pending [`TelemetryReportBase`]s, so that an unreachable
+//! `telemetry.risingwave.dev` (or a crash mid-send) no longer means a report is silently dropped.
+//!
+//! Each [`TelemetryNodeType`] gets its own small WAL file: the single writer appends a
+//! `(version, serialized report)` record before ever attempting the network send, and a separate
+//! watermark file records the highest version the backend has acknowledged. The watermark is only
+//! advanced -- and fsynced -- after a 2xx response, so a crash between "sent" and "acknowledged"
+//! just means the same batch is resent once, which the backend should treat idempotently.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror_ext::AsReport;
+
+use super::{TelemetryNodeType, TelemetryReportBase, TelemetryResult};
+
+/// Upper bound on retry backoff, so a long outage doesn't grow the interval unboundedly.
+const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WalRecord {
+    /// Monotonically increasing, assigned by the single writer under `next_version`.
+    version: u64,
+    report: TelemetryReportBase,
+}
+
+/// The append-only WAL for one node's telemetry reports, plus the watermark of what's been
+/// acknowledged by the backend.
+pub struct TelemetryReportQueue {
+    wal_path: PathBuf,
+    watermark_path: PathBuf,
+    next_version: u64,
+    /// Current retry backoff; reset to [`INITIAL_BACKOFF`] after every successful flush.
+    backoff: Duration,
+}
+
+impl TelemetryReportQueue {
+    /// Opens (creating if absent) the WAL for `node_type` rooted at `data_dir`, replaying the
+    /// on-disk watermark and any still-pending records so that `next_version` continues past the
+    /// highest version the last run wrote.
+    pub fn open(data_dir: &Path, node_type: TelemetryNodeType) -> TelemetryResult<Self> {
+        std::fs::create_dir_all(data_dir)
+            .map_err(|e| format!("failed to create telemetry wal dir: {}", e.as_report()))?;
+
+        let suffix = format!("{:?}", node_type).to_lowercase();
+        let wal_path = data_dir.join(format!("telemetry_{suffix}.wal"));
+        let watermark_path = data_dir.join(format!("telemetry_{suffix}.watermark"));
+
+        let last_acked_version = read_watermark(&watermark_path)?;
+
+        let mut queue = Self {
+            wal_path,
+            watermark_path,
+            next_version: last_acked_version + 1,
+            backoff: INITIAL_BACKOFF,
+        };
+        // Unacknowledged records from a previous run keep their versions; new ones go after them.
+        if let Some(max_pending) = queue.pending_records()?.iter().map(|r| r.version).max() {
+            queue.next_version = max_pending + 1;
+        }
+        Ok(queue)
+    }
+
+    /// Appends `report` to the WAL under the next version, ahead of attempting any network send.
+    pub fn enqueue(&mut self, report: TelemetryReportBase) -> TelemetryResult<()> {
+        let record = WalRecord {
+            version: self.next_version,
+            report,
+        };
+        self.next_version += 1;
+
+        let serialized = serde_json::to_vec(&record)
+            .map_err(|e| format!("failed to serialize telemetry record: {}", e.as_report()))?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.wal_path)
+            .map_err(|e| format!("failed to open telemetry wal: {}", e.as_report()))?;
+        file.write_all(&(serialized.len() as u32).to_le_bytes())
+            .and_then(|_| file.write_all(&serialized))
+            .map_err(|e| format!("failed to append telemetry wal record: {}", e.as_report()))?;
+        Ok(())
+    }
+
+    /// Returns every record with `version` greater than the last-acknowledged watermark, in
+    /// ascending version order, ready to be sent as a single batch.
+    fn pending_records(&self) -> TelemetryResult<Vec<WalRecord>> {
+        let last_acked = read_watermark(&self.watermark_path)?;
+        let mut file = match std::fs::File::open(&self.wal_path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(format!("failed to open telemetry wal: {}", e.as_report())),
+        };
+
+        let mut records = vec![];
+        loop {
+            let mut len_buf = [0u8; 4];
+            match file.read_exact(&mut len_buf) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(format!("failed to read telemetry wal: {}", e.as_report())),
+            }
+            let len = u32::from_le_bytes(len_buf) as usize;
+            let mut payload = vec![0u8; len];
+            file.read_exact(&mut payload)
+                .map_err(|e| format!("failed to read telemetry wal record: {}", e.as_report()))?;
+            let record: WalRecord = serde_json::from_slice(&payload)
+                .map_err(|e| format!("failed to parse telemetry wal record: {}", e.as_report()))?;
+            if record.version > last_acked {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    /// One flush tick: gathers pending records, POSTs them as a single batch via `send_batch`,
+    /// and only on success advances (and fsyncs) the acknowledged-version watermark and truncates
+    /// the WAL up to it. On failure, leaves everything in place and sleeps the current backoff,
+    /// doubling it (capped at [`MAX_BACKOFF`]) for next time.
+    pub async fn flush<F, Fut>(&mut self, send_batch: F) -> TelemetryResult<()>
+    where
+        F: FnOnce(Vec<TelemetryReportBase>) -> Fut,
+        Fut: std::future::Future<Output = TelemetryResult<()>>,
+    {
+        let records = self.pending_records()?;
+        if records.is_empty() {
+            return Ok(());
+        }
+        let max_version = records.iter().map(|r| r.version).max().unwrap();
+        let reports = records.into_iter().map(|r| r.report).collect();
+
+        match send_batch(reports).await {
+            Ok(()) => {
+                write_watermark(&self.watermark_path, max_version)?;
+                self.truncate_acknowledged(max_version)?;
+                self.backoff = INITIAL_BACKOFF;
+                Ok(())
+            }
+            Err(err) => {
+                tokio::time::sleep(self.backoff).await;
+                self.backoff = std::cmp::min(self.backoff * 2, MAX_BACKOFF);
+                Err(err)
+            }
+        }
+    }
+
+    /// Enqueues `report` and immediately tries to flush the queue by POSTing every pending
+    /// report -- this one plus any left over from a previous failed attempt -- to `url` as a
+    /// single batched request via [`super::post_telemetry_report_pb`], the node's actual
+    /// telemetry send path.
+    ///
+    /// Callers on the periodic telemetry tick should use this instead of calling
+    /// [`super::post_telemetry_report_pb`] directly: routing every report through the WAL is what
+    /// makes a report survive an unreachable backend or a crash mid-send, per this module's
+    /// whole point. A failed send still leaves the reports durably enqueued for the next tick.
+    pub async fn report(&mut self, report: TelemetryReportBase, url: &str) -> TelemetryResult<()> {
+        self.enqueue(report)?;
+        self.flush(|reports| async move {
+            let body = serde_json::to_vec(&reports).map_err(|e| {
+                format!("failed to serialize telemetry report batch: {}", e.as_report())
+            })?;
+            super::post_telemetry_report_pb(url, body).await
+        })
+        .await
+    }
+
+    /// Rewrites the WAL file to contain only records with `version > acknowledged_version`, so it
+    /// doesn't grow unboundedly across restarts.
+    fn truncate_acknowledged(&self, acknowledged_version: u64) -> TelemetryResult<()> {
+        let remaining: Vec<WalRecord> = self
+            .pending_records()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|r| r.version > acknowledged_version)
+            .collect();
+
+        let tmp_path = self.wal_path.with_extension("wal.tmp");
+        let mut tmp = std::fs::File::create(&tmp_path)
+            .map_err(|e| format!("failed to create telemetry wal tmp file: {}", e.as_report()))?;
+        for record in &remaining {
+            let serialized = serde_json::to_vec(record)
+                .map_err(|e| format!("failed to serialize telemetry record: {}", e.as_report()))?;
+            tmp.write_all(&(serialized.len() as u32).to_le_bytes())
+                .and_then(|_| tmp.write_all(&serialized))
+                .map_err(|e| format!("failed to write telemetry wal tmp file: {}", e.as_report()))?;
+        }
+        tmp.sync_all()
+            .map_err(|e| format!("failed to fsync telemetry wal tmp file: {}", e.as_report()))?;
+        std::fs::rename(&tmp_path, &self.wal_path)
+            .map_err(|e| format!("failed to replace telemetry wal: {}", e.as_report()))?;
+        Ok(())
+    }
+}
+
+fn read_watermark(path: &Path) -> TelemetryResult<u64> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => s
+            .trim()
+            .parse()
+            .map_err(|e| format!("corrupt telemetry watermark file: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(format!("failed to read telemetry watermark: {}", e.as_report())),
+    }
+}
+
+/// Writes `version` to the watermark file and fsyncs it, so the acknowledgment is durable before
+/// the WAL is truncated on top of it.
+fn write_watermark(path: &Path, version: u64) -> TelemetryResult<()> {
+    let tmp_path = path.with_extension("watermark.tmp");
+    {
+        let mut file = std::fs::File::create(&tmp_path)
+            .map_err(|e| format!("failed to create telemetry watermark tmp file: {}", e.as_report()))?;
+        file.write_all(version.to_string().as_bytes())
+            .map_err(|e| format!("failed to write telemetry watermark: {}", e.as_report()))?;
+        file.sync_all()
+            .map_err(|e| format!("failed to fsync telemetry watermark: {}", e.as_report()))?;
+    }
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("failed to replace telemetry watermark: {}", e.as_report()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::telemetry::current_timestamp;
+
+    fn dummy_report(tracking_id: &str) -> TelemetryReportBase {
+        TelemetryReportBase {
+            tracking_id: tracking_id.to_string(),
+            session_id: "session".to_string(),
+            system_data: Default::default(),
+            up_time: 0,
+            time_stamp: current_timestamp(),
+            node_type: TelemetryNodeType::Meta,
+            is_test: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_advances_watermark_only_on_success() {
+        let dir = tempdir().unwrap();
+        let mut queue = TelemetryReportQueue::open(dir.path(), TelemetryNodeType::Meta).unwrap();
+
+        queue.enqueue(dummy_report("a")).unwrap();
+        queue.enqueue(dummy_report("b")).unwrap();
+
+        // A failing send must not advance the watermark.
+        let err = queue
+            .flush(|_reports| async { Err::<(), _>("network down".to_string()) })
+            .await;
+        assert!(err.is_err());
+        assert_eq!(queue.pending_records().unwrap().len(), 2);
+
+        // A successful send advances the watermark and truncates the WAL.
+        queue
+            .flush(|reports| async move {
+                assert_eq!(reports.len(), 2);
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(queue.pending_records().unwrap().len(), 0);
+
+        // Re-opening from disk should replay the watermark, i.e. see nothing pending.
+        let reopened = TelemetryReportQueue::open(dir.path(), TelemetryNodeType::Meta).unwrap();
+        assert_eq!(reopened.pending_records().unwrap().len(), 0);
+    }
+}
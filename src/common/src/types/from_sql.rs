@@ -12,15 +12,83 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use postgres_types::Kind;
 use risingwave_common::types::{
-    Date, Decimal, Interval, JsonbVal, ScalarImpl, Time, Timestamp, Timestamptz,
+    Date, Decimal, Interval, JsonbVal, ListValue, ScalarImpl, StructValue, Time, Timestamp,
+    Timestamptz,
 };
 
+/// Decodes the PostgreSQL binary array wire format: a header of `ndim` (`i32`), a flags word
+/// (`i32`), the element type OID (`i32`), then `ndim` `(length, lower_bound)` pairs (each two
+/// `i32`s), followed by `length`-prefixed element payloads (a `-1` length payload is SQL `NULL`).
+///
+/// Multi-dimensional arrays (`ndim > 1`) are rejected: RisingWave's `DataType::List` is a single
+/// nesting level, and there is no lossless, unsurprising way to flatten e.g. a `2x3` array into
+/// it.
+fn decode_array(
+    elem_ty: &postgres_types::Type,
+    raw: &[u8],
+) -> Result<Vec<Option<ScalarImpl>>, Box<dyn std::error::Error + Sync + Send>> {
+    let mut buf = raw;
+    let read_i32 = |buf: &mut &[u8]| -> Result<i32, Box<dyn std::error::Error + Sync + Send>> {
+        if buf.len() < 4 {
+            return Err("truncated postgres array header".into());
+        }
+        let (head, rest) = buf.split_at(4);
+        *buf = rest;
+        Ok(i32::from_be_bytes(head.try_into().unwrap()))
+    };
+
+    let ndim = read_i32(&mut buf)?;
+    let _flags = read_i32(&mut buf)?;
+    let _elem_oid = read_i32(&mut buf)?;
+
+    if ndim == 0 {
+        return Ok(vec![]);
+    }
+    if ndim != 1 {
+        return Err(format!(
+            "multi-dimensional postgres arrays (ndim = {}) are not supported",
+            ndim
+        )
+        .into());
+    }
+
+    let length = read_i32(&mut buf)?;
+    let _lower_bound = read_i32(&mut buf)?;
+
+    let mut elements = Vec::with_capacity(length.max(0) as usize);
+    for _ in 0..length {
+        let elem_len = read_i32(&mut buf)?;
+        if elem_len < 0 {
+            // NULL element.
+            elements.push(None);
+            continue;
+        }
+        let elem_len = elem_len as usize;
+        if buf.len() < elem_len {
+            return Err("truncated postgres array element".into());
+        }
+        let (elem_raw, rest) = buf.split_at(elem_len);
+        buf = rest;
+        elements.push(Some(ScalarImpl::from_sql(elem_ty, elem_raw)?));
+    }
+    Ok(elements)
+}
+
 impl<'a> postgres_types::FromSql<'a> for ScalarImpl {
     fn from_sql(
         ty: &postgres_types::Type,
         raw: &'a [u8],
     ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        if let Kind::Array(elem_ty) = ty.kind() {
+            let elements = decode_array(elem_ty, raw)?;
+            return Ok(ScalarImpl::from(ListValue::new(elements)));
+        }
+        if let Kind::Composite(fields) = ty.kind() {
+            return Ok(ScalarImpl::from(decode_composite(fields, raw)?));
+        }
+
         Ok(match *ty {
             postgres_types::Type::BOOL => {
                 ScalarImpl::from(<bool as postgres_types::FromSql>::from_sql(ty, raw)?)
@@ -60,13 +128,20 @@ impl<'a> postgres_types::FromSql<'a> for ScalarImpl {
             postgres_types::Type::VARCHAR | postgres_types::Type::TEXT => {
                 ScalarImpl::from(String::from_sql(ty, raw)?)
             }
-            // Serial, Int256, Struct, List and Decimal are not supported here
+            // Serial, Int256 and Decimal are not supported here.
             // Note: The Decimal type is specially handled in the `ScalarAdapter`.
             _ => bail_not_implemented!("the postgres decoding for {ty} is unsupported"),
         })
     }
 
     fn accepts(ty: &postgres_types::Type) -> bool {
+        if let Kind::Array(elem_ty) = ty.kind() {
+            return ScalarImpl::accepts(elem_ty);
+        }
+        if let Kind::Composite(fields) = ty.kind() {
+            return fields.iter().all(|f| ScalarImpl::accepts(f.type_()));
+        }
+
         matches!(
             *ty,
             postgres_types::Type::BOOL
@@ -88,6 +163,54 @@ impl<'a> postgres_types::FromSql<'a> for ScalarImpl {
     }
 }
 
+/// Decodes the PostgreSQL binary composite/row wire format: a header of `num_fields` (`i32`),
+/// then per field an OID (`i32`) followed by a length-prefixed payload (`-1` length is `NULL`).
+/// Fields are decoded positionally against `fields`, recursing through `ScalarImpl::from_sql`
+/// keyed on each field's own type, same as array elements above.
+fn decode_composite(
+    fields: &[postgres_types::Field],
+    raw: &[u8],
+) -> Result<StructValue, Box<dyn std::error::Error + Sync + Send>> {
+    let mut buf = raw;
+    let read_i32 = |buf: &mut &[u8]| -> Result<i32, Box<dyn std::error::Error + Sync + Send>> {
+        if buf.len() < 4 {
+            return Err("truncated postgres composite header".into());
+        }
+        let (head, rest) = buf.split_at(4);
+        *buf = rest;
+        Ok(i32::from_be_bytes(head.try_into().unwrap()))
+    };
+
+    let num_fields = read_i32(&mut buf)?;
+    if num_fields as usize != fields.len() {
+        return Err(format!(
+            "postgres composite has {} fields but target struct has {}",
+            num_fields,
+            fields.len()
+        )
+        .into());
+    }
+
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        let _field_oid = read_i32(&mut buf)?;
+        let field_len = read_i32(&mut buf)?;
+        if field_len < 0 {
+            values.push(None);
+            continue;
+        }
+        let field_len = field_len as usize;
+        if buf.len() < field_len {
+            return Err("truncated postgres composite field".into());
+        }
+        let (field_raw, rest) = buf.split_at(field_len);
+        buf = rest;
+        values.push(Some(ScalarImpl::from_sql(field.type_(), field_raw)?));
+    }
+
+    Ok(StructValue::new(values))
+}
+
 /// The following table shows the mapping between Rust types and Sql Server types in tiberius.
 /// |Rust Type|Sql Server Type|
 /// |`u8`|`tinyint`|
@@ -15,9 +15,137 @@
 use std::error::Error;
 
 use bytes::BytesMut;
-use postgres_types::{to_sql_checked, IsNull};
+use postgres_types::{to_sql_checked, IsNull, Kind};
 
-use crate::types::ScalarImpl;
+use crate::types::{Int256, ScalarImpl};
+
+/// Writes `value`'s `to_sql` encoding length-prefixed by an `i32` byte count (`-1` for `NULL`),
+/// the shape every element of a PostgreSQL array or composite uses. Shared by [`encode_array`]
+/// and [`encode_composite`].
+fn write_length_prefixed(
+    value: Option<&ScalarImpl>,
+    ty: &postgres_types::Type,
+    out: &mut BytesMut,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let Some(value) = value else {
+        out.extend_from_slice(&(-1i32).to_be_bytes());
+        return Ok(());
+    };
+
+    let len_idx = out.len();
+    out.extend_from_slice(&0i32.to_be_bytes());
+    let value_start = out.len();
+    value.to_sql(ty, out)?;
+    let value_len = (out.len() - value_start) as i32;
+    out[len_idx..len_idx + 4].copy_from_slice(&value_len.to_be_bytes());
+    Ok(())
+}
+
+/// Encodes `elements` into the PostgreSQL binary array wire format: an `ndim` (`i32`), a flags
+/// word (`i32`, the `HASNULL` bit set if any element is `NULL`), the element type OID (`i32`),
+/// then (RisingWave's `DataType::List` is always a single dimension) one `(length, lower_bound)`
+/// pair of `i32`s, followed by `length`-prefixed element payloads (a `-1` length for `NULL`).
+/// Mirrors `decode_array` in `from_sql.rs`, but in reverse.
+fn encode_array(
+    elem_ty: &postgres_types::Type,
+    elements: &[Option<ScalarImpl>],
+    out: &mut BytesMut,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    if elements.is_empty() {
+        out.extend_from_slice(&0i32.to_be_bytes()); // ndim
+        out.extend_from_slice(&0i32.to_be_bytes()); // flags
+        out.extend_from_slice(&(elem_ty.oid() as i32).to_be_bytes());
+        return Ok(());
+    }
+
+    let has_null = elements.iter().any(|e| e.is_none());
+    out.extend_from_slice(&1i32.to_be_bytes()); // ndim
+    out.extend_from_slice(&(has_null as i32).to_be_bytes()); // flags
+    out.extend_from_slice(&(elem_ty.oid() as i32).to_be_bytes());
+    out.extend_from_slice(&(elements.len() as i32).to_be_bytes()); // dimension length
+    out.extend_from_slice(&1i32.to_be_bytes()); // lower bound
+
+    for element in elements {
+        write_length_prefixed(element.as_ref(), elem_ty, out)?;
+    }
+    Ok(())
+}
+
+/// Encodes `values` into the PostgreSQL binary composite (`ROW`) wire format: an `i32` field
+/// count, then per field an `i32` type OID followed by a length-prefixed value (a `-1` length for
+/// `NULL`). Field order and OIDs are taken positionally from `fields`, i.e. the target composite
+/// type's own definition. Mirrors `decode_composite` in `from_sql.rs`, but in reverse.
+fn encode_composite(
+    fields: &[postgres_types::Field],
+    values: &[Option<ScalarImpl>],
+    out: &mut BytesMut,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    if fields.len() != values.len() {
+        return Err(format!(
+            "struct value has {} fields but target composite type has {}",
+            values.len(),
+            fields.len()
+        )
+        .into());
+    }
+
+    out.extend_from_slice(&(fields.len() as i32).to_be_bytes());
+    for (field, value) in fields.iter().zip(values) {
+        out.extend_from_slice(&(field.type_().oid() as i32).to_be_bytes());
+        write_length_prefixed(value.as_ref(), field.type_(), out)?;
+    }
+    Ok(())
+}
+
+/// Encodes an [`Int256`] into PostgreSQL's binary `numeric` wire format: a header of four `i16`
+/// fields — `ndigits`, `weight` (power-of-10000 position of the most significant digit group),
+/// `sign` (`0x0000` positive, `0x4000` negative), and `dscale` (`0`, an `Int256` has no fractional
+/// digits) — followed by `ndigits` base-10000 digit groups, most significant first.
+///
+/// The digit groups are derived from `value`'s decimal string rather than its internal
+/// representation, since base-10000 chunking is naturally a decimal-digit operation.
+fn encode_int256(value: &Int256, out: &mut BytesMut) {
+    let text = value.to_string();
+    let (sign, magnitude) = match text.strip_prefix('-') {
+        Some(rest) => (0x4000u16, rest),
+        None => (0x0000u16, text.as_str()),
+    };
+
+    // Left-pad with zeros so the digit count is a multiple of 4, then split into big-endian
+    // base-10000 groups.
+    let pad = (4 - magnitude.len() % 4) % 4;
+    let padded: Vec<u8> = std::iter::repeat(b'0')
+        .take(pad)
+        .chain(magnitude.bytes())
+        .collect();
+    let mut groups: Vec<i16> = padded
+        .chunks_exact(4)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap().parse().unwrap())
+        .collect();
+    let mut weight = groups.len() as i16 - 1;
+
+    // Leading zero groups don't shift the value, but each one removed lowers the weight of the
+    // group that's now first; trailing zero groups can simply be dropped.
+    while groups.len() > 1 && groups[0] == 0 {
+        groups.remove(0);
+        weight -= 1;
+    }
+    while groups.len() > 1 && *groups.last().unwrap() == 0 {
+        groups.pop();
+    }
+    if groups == [0] {
+        groups.clear();
+        weight = 0;
+    }
+
+    out.extend_from_slice(&(groups.len() as i16).to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&sign.to_be_bytes());
+    out.extend_from_slice(&0i16.to_be_bytes()); // dscale
+    for group in groups {
+        out.extend_from_slice(&group.to_be_bytes());
+    }
+}
 
 impl postgres_types::ToSql for ScalarImpl {
     to_sql_checked!();
@@ -47,8 +175,23 @@ impl postgres_types::ToSql for ScalarImpl {
             ScalarImpl::Time(v) => v.to_sql(ty, out),
             ScalarImpl::Bytea(v) => (&**v).to_sql(ty, out),
             ScalarImpl::Jsonb(v) => v.to_sql(ty, out),
-            ScalarImpl::Int256(_) | ScalarImpl::Struct(_) | ScalarImpl::List(_) => {
-                bail_not_implemented!("the postgres encoding for {ty} is unsupported")
+            ScalarImpl::List(v) => {
+                let Kind::Array(elem_ty) = ty.kind() else {
+                    bail_not_implemented!("the postgres encoding for {ty} is unsupported");
+                };
+                encode_array(elem_ty, v.values(), out)?;
+                Ok(IsNull::No)
+            }
+            ScalarImpl::Struct(v) => {
+                let Kind::Composite(fields) = ty.kind() else {
+                    bail_not_implemented!("the postgres encoding for {ty} is unsupported");
+                };
+                encode_composite(fields, v.fields(), out)?;
+                Ok(IsNull::No)
+            }
+            ScalarImpl::Int256(v) => {
+                encode_int256(v, out);
+                Ok(IsNull::No)
             }
         }
     }
@@ -123,9 +266,12 @@ impl<'a> tiberius::IntoSql<'a> for ScalarImpl {
             ScalarImpl::Timestamp(v) => v.into_sql(),
             ScalarImpl::Timestamptz(v) => v.to_datetime_utc().into_sql(),
             ScalarImpl::Time(v) => v.into_sql(),
-            // ScalarImpl::Bytea(v) => (*v.clone()).into_sql(),
+            ScalarImpl::Utf8(v) => String::from(v).into_sql(),
+            ScalarImpl::Bytea(v) => v.into_vec().into_sql(),
+            ScalarImpl::Jsonb(v) => v.to_string().into_sql(),
+            ScalarImpl::Serial(v) => v.into_inner().into_sql(),
             value => {
-                // Utf8, Serial, Interval, Timestamptz, Jsonb, Int256, Struct, List are not supported yet
+                // Interval, Int256, Struct, List are not supported yet
                 unimplemented!("the sql server decoding for {:?} is unsupported", value);
             }
         }
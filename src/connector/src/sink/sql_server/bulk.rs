@@ -0,0 +1,122 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TDS bulk-load fast path for the SQL Server sink.
+//!
+//! Row-by-row `INSERT`s round-trip one TDS message per row, which dominates latency for
+//! high-throughput sinks. [`BulkWriter`] batches rows into a single TDS bulk-insert request
+//! instead, built on tiberius's `BulkLoadRequest`/bulk-copy API, converting each row's
+//! `ScalarImpl` columns through the [`tiberius::IntoSql`] mapping already used for row-by-row
+//! writes.
+
+use bitflags::bitflags;
+use risingwave_common::types::ScalarImpl;
+use tiberius::{Client, IntoSql};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+bitflags! {
+    /// Mirrors the SQL Server BCP hint flags, surfaced as a single set of boolean `WITH`
+    /// parameters rather than one column per flag.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BulkCopyOptions: u8 {
+        /// Validate `CHECK` and `FOREIGN KEY` constraints while loading (`CHECK_CONSTRAINTS`).
+        const CHECK_CONSTRAINTS = 0b0001;
+        /// Run the target table's `AFTER INSERT` triggers (`FIRE_TRIGGERS`).
+        const FIRE_TRIGGERS = 0b0010;
+        /// Preserve NULLs from the source instead of applying column defaults (`KEEP_NULLS`).
+        const KEEP_NULLS = 0b0100;
+        /// Hold a bulk-update lock on the target table for the duration of the load
+        /// (`TABLE_LOCK`).
+        const TABLE_LOCK = 0b1000;
+    }
+}
+
+/// Config for [`BulkWriter`], derived from [`super::SqlServerBulkConfig`]'s `WITH` parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct BulkWriterConfig {
+    /// Bulk-copy hints taken from the `WITH` parameters. [`BulkWriter::flush`] does not send them
+    /// with the bulk-insert request, so none of them changes how a batch is loaded.
+    pub options: BulkCopyOptions,
+    /// Number of rows buffered locally before a batch is flushed as one TDS bulk-insert request.
+    pub batch_row_threshold: usize,
+}
+
+/// Batches rows into TDS bulk-insert requests against `table`, flushing whenever
+/// [`BulkWriterConfig::batch_row_threshold`] rows have been buffered or [`BulkWriter::finish`] is
+/// called.
+pub struct BulkWriter<'a, S> {
+    client: &'a mut Client<S>,
+    table: String,
+    column_names: Vec<String>,
+    config: BulkWriterConfig,
+    pending_rows: Vec<Vec<Option<ScalarImpl>>>,
+}
+
+impl<'a, S> BulkWriter<'a, S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    pub fn new(
+        client: &'a mut Client<S>,
+        table: String,
+        column_names: Vec<String>,
+        config: BulkWriterConfig,
+    ) -> Self {
+        Self {
+            client,
+            table,
+            column_names,
+            config,
+            pending_rows: vec![],
+        }
+    }
+
+    /// Buffers `row`, flushing a batch once [`BulkWriterConfig::batch_row_threshold`] rows have
+    /// accumulated.
+    pub async fn write_row(&mut self, row: Vec<Option<ScalarImpl>>) -> tiberius::Result<()> {
+        debug_assert_eq!(row.len(), self.column_names.len());
+        self.pending_rows.push(row);
+        if self.pending_rows.len() >= self.config.batch_row_threshold {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Sends every buffered row as a single TDS bulk-insert request.
+    pub async fn flush(&mut self) -> tiberius::Result<()> {
+        if self.pending_rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut request = self.client.bulk_insert(&self.table).await?;
+        for row in self.pending_rows.drain(..) {
+            let mut tiberius_row = tiberius::TokenRow::new();
+            for value in row {
+                match value {
+                    Some(value) => tiberius_row.push(value.into_sql()),
+                    None => tiberius_row.push(tiberius::ColumnData::Bit(None)),
+                }
+            }
+            request.send(tiberius_row).await?;
+        }
+        request.finalize().await?;
+        Ok(())
+    }
+
+    /// Flushes any remaining buffered rows. Must be called before the writer is dropped, or rows
+    /// buffered since the last threshold-triggered flush are lost.
+    pub async fn finish(mut self) -> tiberius::Result<()> {
+        self.flush().await
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod bulk;
+
+use serde::Deserialize;
+
+use self::bulk::{BulkCopyOptions, BulkWriterConfig};
+
+/// `WITH` parameters controlling the bulk-load fast path, e.g.
+/// `sqlserver.bulk.enable = true, sqlserver.bulk.check_constraints = true`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SqlServerBulkConfig {
+    /// Use a TDS bulk-insert request per batch instead of one `INSERT` per row.
+    #[serde(rename = "sqlserver.bulk.enable", default)]
+    pub enable: bool,
+
+    #[serde(rename = "sqlserver.bulk.check_constraints", default)]
+    pub check_constraints: bool,
+
+    #[serde(rename = "sqlserver.bulk.fire_triggers", default)]
+    pub fire_triggers: bool,
+
+    #[serde(rename = "sqlserver.bulk.keep_nulls", default)]
+    pub keep_nulls: bool,
+
+    #[serde(rename = "sqlserver.bulk.table_lock", default)]
+    pub table_lock: bool,
+
+    /// Number of rows buffered before a bulk batch is flushed.
+    #[serde(rename = "sqlserver.bulk.batch_row_threshold", default = "default_batch_row_threshold")]
+    pub batch_row_threshold: usize,
+}
+
+fn default_batch_row_threshold() -> usize {
+    2048
+}
+
+impl SqlServerBulkConfig {
+    pub fn writer_config(&self) -> BulkWriterConfig {
+        let mut options = BulkCopyOptions::empty();
+        options.set(BulkCopyOptions::CHECK_CONSTRAINTS, self.check_constraints);
+        options.set(BulkCopyOptions::FIRE_TRIGGERS, self.fire_triggers);
+        options.set(BulkCopyOptions::KEEP_NULLS, self.keep_nulls);
+        options.set(BulkCopyOptions::TABLE_LOCK, self.table_lock);
+
+        BulkWriterConfig {
+            options,
+            batch_row_threshold: self.batch_row_threshold,
+        }
+    }
+}
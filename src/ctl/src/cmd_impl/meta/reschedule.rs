@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::process::exit;
 
 use anyhow::{anyhow, Result};
@@ -23,6 +23,7 @@ use risingwave_meta::manager::WorkerId;
 use risingwave_pb::common::WorkerNode;
 use risingwave_pb::meta::table_fragments::ActorStatus;
 use risingwave_pb::meta::{GetClusterInfoResponse, PbWorkerReschedule};
+use risingwave_rpc_client::MetaClient;
 use serde::{Deserialize, Serialize};
 use thiserror_ext::AsReport;
 
@@ -103,6 +104,8 @@ pub async fn reschedule(
     from: Option<String>,
     dry_run: bool,
     resolve_no_shuffle: bool,
+    export: Option<String>,
+    save_undo: Option<String>,
 ) -> Result<()> {
     let meta_client = context.meta_client().await?;
 
@@ -127,7 +130,165 @@ pub async fn reschedule(
         _ => unreachable!(),
     };
 
-    for (fragment_id, reschedule) in &reschedules {
+    print_reschedule_plan(&reschedules);
+
+    if let Some(path) = export {
+        write_reschedule_payload(&path, &reschedules, revision)?;
+        println!("Plan exported to {}", path);
+    }
+
+    if let Some(path) = save_undo {
+        let undo_plan = invert_reschedule_plan(&reschedules);
+        write_reschedule_payload(&path, &undo_plan, revision)?;
+        println!("Undo plan saved to {}", path);
+    }
+
+    apply_reschedule_plan(&meta_client, reschedules, revision, dry_run, resolve_no_shuffle).await
+}
+
+/// Derives a reschedule plan from the cluster's current state instead of requiring the operator
+/// to hand-author one: for every fragment, spreads its actors as evenly as possible across the
+/// `eligible` workers, draining any worker not in that set down to zero.
+///
+/// Workers are considered eligible when `include_workers` is empty or contains them, and
+/// `exclude_workers` does not; this lets operators cordon off nodes that are being retired
+/// without having to enumerate every fragment by hand.
+pub async fn auto_rebalance(
+    context: &CtlContext,
+    include_workers: Vec<u32>,
+    exclude_workers: Vec<u32>,
+    dry_run: bool,
+    resolve_no_shuffle: bool,
+    export: Option<String>,
+) -> Result<()> {
+    let meta_client = context.meta_client().await?;
+
+    let GetClusterInfoResponse {
+        worker_nodes,
+        table_fragments,
+        revision,
+        ..
+    } = match meta_client.get_cluster_info().await {
+        Ok(info) => info,
+        Err(e) => {
+            println!("Failed to get cluster info: {}", e.as_report());
+            exit(1);
+        }
+    };
+
+    let include_workers: HashSet<_> = include_workers.into_iter().collect();
+    let exclude_workers: HashSet<_> = exclude_workers.into_iter().collect();
+
+    let mut eligible_worker_ids: Vec<_> = worker_nodes
+        .iter()
+        .map(|worker| worker.id)
+        .filter(|id| include_workers.is_empty() || include_workers.contains(id))
+        .filter(|id| !exclude_workers.contains(id))
+        .collect();
+    eligible_worker_ids.sort_unstable();
+
+    if eligible_worker_ids.is_empty() {
+        return Err(anyhow!("no eligible worker to rebalance onto"));
+    }
+
+    let mut reschedules = HashMap::new();
+
+    for fragments in &table_fragments {
+        for (fragment_id, fragment) in &fragments.fragments {
+            let mut current_actor_count: HashMap<WorkerId, usize> = HashMap::new();
+            for actor in &fragment.actors {
+                let worker_id = fragments
+                    .actor_status
+                    .get(&actor.actor_id)
+                    .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
+                    .unwrap()
+                    .worker_node_id;
+                *current_actor_count.entry(worker_id).or_default() += 1;
+            }
+
+            if let Some(reschedule) =
+                compute_balanced_reschedule(&current_actor_count, &eligible_worker_ids)
+            {
+                reschedules.insert(*fragment_id, reschedule);
+            }
+        }
+    }
+
+    print_reschedule_plan(&reschedules);
+
+    if let Some(path) = export {
+        write_reschedule_payload(&path, &reschedules, revision)?;
+        println!("Plan exported to {}", path);
+    }
+
+    apply_reschedule_plan(&meta_client, reschedules, revision, dry_run, resolve_no_shuffle).await
+}
+
+/// Computes the per-worker actor delta that moves `current_actor_count` to an even spread over
+/// `eligible_worker_ids`, or `None` if the fragment is already balanced.
+///
+/// Every eligible worker is targeted for `actor_count / eligible_worker_ids.len()` actors, with
+/// the remainder handed out one-by-one to the lowest-numbered eligible workers; any worker absent
+/// from `eligible_worker_ids` (e.g. one being retired) is targeted for zero and fully drained.
+fn compute_balanced_reschedule(
+    current_actor_count: &HashMap<WorkerId, usize>,
+    eligible_worker_ids: &[WorkerId],
+) -> Option<PbWorkerReschedule> {
+    let actor_count: usize = current_actor_count.values().sum();
+    let target_actor_count = compute_target_actor_count(actor_count, eligible_worker_ids);
+
+    let mut decreased_actor_count = HashMap::new();
+    let mut increased_actor_count = HashMap::new();
+
+    let touched_worker_ids: HashSet<_> = current_actor_count
+        .keys()
+        .chain(target_actor_count.keys())
+        .collect();
+
+    for worker_id in touched_worker_ids {
+        let current = *current_actor_count.get(worker_id).unwrap_or(&0);
+        let target = *target_actor_count.get(worker_id).unwrap_or(&0);
+
+        match current.cmp(&target) {
+            std::cmp::Ordering::Greater => {
+                decreased_actor_count.insert(*worker_id, current - target);
+            }
+            std::cmp::Ordering::Less => {
+                increased_actor_count.insert(*worker_id, target - current);
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+
+    if decreased_actor_count.is_empty() && increased_actor_count.is_empty() {
+        return None;
+    }
+
+    Some(PbWorkerReschedule {
+        decreased_actor_count,
+        increased_actor_count,
+    })
+}
+
+/// Splits `actor_count` as evenly as possible over `eligible_worker_ids`: every worker gets
+/// `actor_count / eligible_worker_ids.len()`, and the remainder is handed out one-by-one to the
+/// lowest-numbered workers.
+fn compute_target_actor_count(
+    actor_count: usize,
+    eligible_worker_ids: &[WorkerId],
+) -> HashMap<WorkerId, usize> {
+    let base = actor_count / eligible_worker_ids.len();
+    let remainder = actor_count % eligible_worker_ids.len();
+
+    eligible_worker_ids
+        .iter()
+        .enumerate()
+        .map(|(i, worker_id)| (*worker_id, base + usize::from(i < remainder)))
+        .collect()
+}
+
+fn print_reschedule_plan(reschedules: &HashMap<u32, PbWorkerReschedule>) {
+    for (fragment_id, reschedule) in reschedules {
         println!("For fragment #{}", fragment_id);
         if !reschedule.decreased_actor_count.is_empty() {
             println!("\tDecreased: {:?}", reschedule.decreased_actor_count);
@@ -139,25 +300,74 @@ pub async fn reschedule(
 
         println!();
     }
+}
+
+/// Swaps `increased_actor_count` and `decreased_actor_count` for every fragment in `reschedules`,
+/// yielding the plan that undoes it.
+fn invert_reschedule_plan(
+    reschedules: &HashMap<u32, PbWorkerReschedule>,
+) -> HashMap<u32, PbWorkerReschedule> {
+    reschedules
+        .iter()
+        .map(|(fragment_id, reschedule)| {
+            (
+                *fragment_id,
+                PbWorkerReschedule {
+                    increased_actor_count: reschedule.decreased_actor_count.clone(),
+                    decreased_actor_count: reschedule.increased_actor_count.clone(),
+                },
+            )
+        })
+        .collect()
+}
 
-    if !dry_run {
-        println!("---------------------------");
-        let (success, revision) = meta_client
-            .reschedule(reschedules, revision, resolve_no_shuffle)
-            .await?;
+/// Writes `reschedules` to `path` as a [`ReschedulePayload`], in the same `serde_yaml` format
+/// `reschedule`'s `from` branch reads back, so a plan can be reviewed, edited, and replayed later.
+fn write_reschedule_payload(
+    path: &str,
+    reschedules: &HashMap<u32, PbWorkerReschedule>,
+    revision: u64,
+) -> Result<()> {
+    let payload = ReschedulePayload {
+        reschedule_revision: revision,
+        worker_reschedule_plan: reschedules
+            .iter()
+            .map(|(fragment_id, reschedule)| (*fragment_id, reschedule.clone().into()))
+            .collect(),
+    };
 
-        if !success {
-            println!(
-                "Reschedule failed, please check the plan or the revision, current revision is {}",
-                revision
-            );
+    let file = std::fs::File::create(path)?;
+    serde_yaml::to_writer(file, &payload)?;
+    Ok(())
+}
 
-            return Err(anyhow!("reschedule failed"));
-        }
+async fn apply_reschedule_plan(
+    meta_client: &MetaClient,
+    reschedules: HashMap<u32, PbWorkerReschedule>,
+    revision: u64,
+    dry_run: bool,
+    resolve_no_shuffle: bool,
+) -> Result<()> {
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("---------------------------");
+    let (success, revision) = meta_client
+        .reschedule(reschedules, revision, resolve_no_shuffle)
+        .await?;
+
+    if !success {
+        println!(
+            "Reschedule failed, please check the plan or the revision, current revision is {}",
+            revision
+        );
 
-        println!("Reschedule success, current revision is {}", revision);
+        return Err(anyhow!("reschedule failed"));
     }
 
+    println!("Reschedule success, current revision is {}", revision);
+
     Ok(())
 }
 
@@ -222,18 +432,118 @@ fn parse_plan(plan: String) -> Result<HashMap<u32, PbWorkerReschedule>> {
     Ok(reschedules)
 }
 
+/// Builds and applies a reschedule plan that moves every actor off `target_worker_ids` onto the
+/// remaining workers, then blocks until `get_cluster_info` confirms the target workers are
+/// unoccupied, so the caller can safely delete them next.
+async fn drain_target_workers(
+    meta_client: &MetaClient,
+    all_table_fragments: &[risingwave_pb::meta::TableFragments],
+    worker_ids: &HashSet<WorkerId>,
+    target_worker_ids: &HashSet<WorkerId>,
+    revision: u64,
+) -> Result<()> {
+    let mut eligible_worker_ids: Vec<_> =
+        worker_ids.difference(target_worker_ids).copied().collect();
+    eligible_worker_ids.sort_unstable();
+
+    if eligible_worker_ids.is_empty() {
+        return Err(anyhow!(
+            "cannot drain {:?}: no other worker to move their actors to",
+            target_worker_ids
+        ));
+    }
+
+    let mut reschedules = HashMap::new();
+
+    for table_fragments in all_table_fragments {
+        for (fragment_id, fragment) in &table_fragments.fragments {
+            let mut current_actor_count: HashMap<WorkerId, usize> = HashMap::new();
+            for actor in &fragment.actors {
+                let worker_id = table_fragments
+                    .actor_status
+                    .get(&actor.actor_id)
+                    .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
+                    .unwrap()
+                    .worker_node_id;
+                *current_actor_count.entry(worker_id).or_default() += 1;
+            }
+
+            if !current_actor_count
+                .keys()
+                .any(|worker_id| target_worker_ids.contains(worker_id))
+            {
+                continue;
+            }
+
+            if let Some(reschedule) =
+                compute_balanced_reschedule(&current_actor_count, &eligible_worker_ids)
+            {
+                reschedules.insert(*fragment_id, reschedule);
+            }
+        }
+    }
+
+    if reschedules.is_empty() {
+        return Ok(());
+    }
+
+    println!("Draining actors off workers {:?}", target_worker_ids);
+    print_reschedule_plan(&reschedules);
+
+    let (success, revision) = meta_client
+        .reschedule(reschedules, revision, true)
+        .await?;
+    if !success {
+        return Err(anyhow!(
+            "drain reschedule failed, please check the plan or the revision, current revision is {}",
+            revision
+        ));
+    }
+
+    loop {
+        let GetClusterInfoResponse {
+            table_fragments, ..
+        } = meta_client.get_cluster_info().await?;
+
+        let still_occupied = table_fragments.iter().any(|table_fragments| {
+            table_fragments.fragments.values().any(|fragment| {
+                fragment.actors.iter().any(|actor| {
+                    table_fragments
+                        .actor_status
+                        .get(&actor.actor_id)
+                        .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
+                        .is_some_and(|pu| target_worker_ids.contains(&pu.worker_node_id))
+                })
+            })
+        });
+
+        if !still_occupied {
+            break;
+        }
+
+        println!("Waiting for actors to drain off {:?}...", target_worker_ids);
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    println!("Drained workers {:?}", target_worker_ids);
+
+    Ok(())
+}
+
 pub async fn unregister_workers(
     context: &CtlContext,
     workers: Vec<String>,
     yes: bool,
     ignore_not_found: bool,
     check_fragment_occupied: bool,
+    drain: bool,
 ) -> Result<()> {
     let meta_client = context.meta_client().await?;
 
     let GetClusterInfoResponse {
         worker_nodes,
         table_fragments: all_table_fragments,
+        revision,
         ..
     } = match meta_client.get_cluster_info().await {
         Ok(info) => info,
@@ -292,31 +602,42 @@ pub async fn unregister_workers(
         .filter(|worker| target_worker_ids.contains(&worker.id))
         .collect_vec();
 
-    for table_fragments in &all_table_fragments {
-        for (fragment_id, fragment) in &table_fragments.fragments {
-            let occupied_worker_ids: HashSet<_> = fragment
-                .actors
-                .iter()
-                .map(|actor| {
-                    table_fragments
-                        .actor_status
-                        .get(&actor.actor_id)
-                        .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
-                        .unwrap()
-                        .worker_node_id
-                })
-                .collect();
+    if drain {
+        drain_target_workers(
+            &meta_client,
+            &all_table_fragments,
+            &worker_ids,
+            &target_worker_ids,
+            revision,
+        )
+        .await?;
+    } else {
+        for table_fragments in &all_table_fragments {
+            for (fragment_id, fragment) in &table_fragments.fragments {
+                let occupied_worker_ids: HashSet<_> = fragment
+                    .actors
+                    .iter()
+                    .map(|actor| {
+                        table_fragments
+                            .actor_status
+                            .get(&actor.actor_id)
+                            .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
+                            .unwrap()
+                            .worker_node_id
+                    })
+                    .collect();
 
-            let intersection_worker_ids: HashSet<_> = occupied_worker_ids
-                .intersection(&target_worker_ids)
-                .collect();
+                let intersection_worker_ids: HashSet<_> = occupied_worker_ids
+                    .intersection(&target_worker_ids)
+                    .collect();
 
-            if check_fragment_occupied && !intersection_worker_ids.is_empty() {
-                println!(
-                    "worker ids {:?} are still occupied by fragment #{}",
-                    intersection_worker_ids, fragment_id
-                );
-                exit(1);
+                if check_fragment_occupied && !intersection_worker_ids.is_empty() {
+                    println!(
+                        "worker ids {:?} are still occupied by fragment #{}",
+                        intersection_worker_ids, fragment_id
+                    );
+                    exit(1);
+                }
             }
         }
     }
@@ -359,3 +680,111 @@ pub async fn unregister_workers(
 
     Ok(())
 }
+
+/// Per-worker snapshot reported by [`worker_status`]: how many actors it hosts, which fragments
+/// those actors belong to, and how far it sits from the even-distribution target.
+#[derive(Serialize, Debug)]
+pub struct WorkerStatus {
+    pub id: u32,
+    pub host: String,
+    pub actor_count: usize,
+    pub fragment_ids: Vec<u32>,
+    /// Actors above (positive) or below (negative) what this worker would hold if every
+    /// fragment's actors were spread evenly across all workers.
+    pub balance_delta: i64,
+}
+
+/// Read-only report fusing the per-worker actor load and fragment occupancy this module already
+/// derives while planning reschedules, so operators can decide what to feed into `reschedule` or
+/// which workers are safe to `unregister` without hand-deriving it themselves.
+pub async fn worker_status(context: &CtlContext, format: String) -> Result<()> {
+    let meta_client = context.meta_client().await?;
+
+    let GetClusterInfoResponse {
+        worker_nodes,
+        table_fragments: all_table_fragments,
+        ..
+    } = match meta_client.get_cluster_info().await {
+        Ok(info) => info,
+        Err(e) => {
+            println!("Failed to get cluster info: {}", e.as_report());
+            exit(1);
+        }
+    };
+
+    let mut all_worker_ids: Vec<_> = worker_nodes.iter().map(|worker| worker.id).collect();
+    all_worker_ids.sort_unstable();
+
+    let mut actor_count: HashMap<WorkerId, usize> = HashMap::new();
+    let mut fragment_ids: HashMap<WorkerId, BTreeSet<u32>> = HashMap::new();
+    let mut balance_delta: HashMap<WorkerId, i64> = HashMap::new();
+
+    for table_fragments in &all_table_fragments {
+        for (fragment_id, fragment) in &table_fragments.fragments {
+            let mut fragment_actor_count: HashMap<WorkerId, usize> = HashMap::new();
+            for actor in &fragment.actors {
+                let worker_id = table_fragments
+                    .actor_status
+                    .get(&actor.actor_id)
+                    .and_then(|ActorStatus { parallel_unit, .. }| parallel_unit.clone())
+                    .unwrap()
+                    .worker_node_id;
+
+                *actor_count.entry(worker_id).or_default() += 1;
+                *fragment_actor_count.entry(worker_id).or_default() += 1;
+                fragment_ids.entry(worker_id).or_default().insert(*fragment_id);
+            }
+
+            if all_worker_ids.is_empty() {
+                continue;
+            }
+
+            let total: usize = fragment_actor_count.values().sum();
+            let target_actor_count = compute_target_actor_count(total, &all_worker_ids);
+            for worker_id in &all_worker_ids {
+                let current = *fragment_actor_count.get(worker_id).unwrap_or(&0) as i64;
+                let target = *target_actor_count.get(worker_id).unwrap_or(&0) as i64;
+                *balance_delta.entry(*worker_id).or_default() += current - target;
+            }
+        }
+    }
+
+    let statuses: Vec<WorkerStatus> = worker_nodes
+        .iter()
+        .map(|worker| {
+            let host = worker
+                .get_host()
+                .map(|host| format!("{}:{}", host.host, host.port))
+                .unwrap_or_default();
+
+            WorkerStatus {
+                id: worker.id,
+                host,
+                actor_count: *actor_count.get(&worker.id).unwrap_or(&0),
+                fragment_ids: fragment_ids
+                    .get(&worker.id)
+                    .map(|ids| ids.iter().copied().collect())
+                    .unwrap_or_default(),
+                balance_delta: *balance_delta.get(&worker.id).unwrap_or(&0),
+            }
+        })
+        .collect();
+
+    match format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&statuses)?),
+        _ => {
+            println!(
+                "{:<6} {:<22} {:<8} {:<8} {}",
+                "ID", "HOST", "ACTORS", "BALANCE", "FRAGMENTS"
+            );
+            for status in &statuses {
+                println!(
+                    "{:<6} {:<22} {:<8} {:<8} {:?}",
+                    status.id, status.host, status.actor_count, status.balance_delta, status.fragment_ids
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
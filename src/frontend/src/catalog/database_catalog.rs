@@ -0,0 +1,56 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::catalog::schema_catalog::SchemaCatalog;
+use crate::catalog::DatabaseId;
+
+/// One database within the root [`super::root_catalog::Catalog`]: a namespace of schemas, keyed
+/// by schema name since lookups (`SchemaPath` resolution, `search_path`) are always by name.
+pub struct DatabaseCatalog {
+    id: DatabaseId,
+    name: String,
+    schemas: HashMap<String, SchemaCatalog>,
+}
+
+impl DatabaseCatalog {
+    pub fn new(id: DatabaseId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            schemas: HashMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> DatabaseId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn create_schema(&mut self, schema: SchemaCatalog) {
+        self.schemas.insert(schema.name().to_string(), schema);
+    }
+
+    pub fn get_schema_mut(&mut self, schema_name: &str) -> Option<&mut SchemaCatalog> {
+        self.schemas.get_mut(schema_name)
+    }
+
+    pub fn iter_schemas(&self) -> impl Iterator<Item = &SchemaCatalog> {
+        self.schemas.values()
+    }
+}
@@ -37,6 +37,7 @@ pub(crate) mod table_catalog;
 pub(crate) mod view_catalog;
 
 pub use index_catalog::IndexCatalog;
+pub(crate) use system_catalog::register_system_catalogs;
 pub use table_catalog::TableCatalog;
 
 use crate::user::UserId;
@@ -0,0 +1,83 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
+
+use crate::catalog::database_catalog::DatabaseCatalog;
+use crate::catalog::schema_catalog::SchemaCatalog;
+use crate::catalog::system_catalog::register_system_catalogs;
+use crate::catalog::table_catalog::TableCatalog;
+use crate::catalog::DatabaseId;
+use crate::user::UserId;
+
+/// The root of the frontend's in-memory catalog: every database this cluster knows about, each
+/// holding its own namespace of schemas. Accessed through [`super::catalog_service::CatalogReader`]
+/// / [`super::catalog_service::CatalogWriter`], which keep it in sync with the meta-persisted
+/// catalog as DDL commits.
+#[derive(Default)]
+pub struct Catalog {
+    databases: HashMap<DatabaseId, DatabaseCatalog>,
+    database_ids_by_name: HashMap<String, DatabaseId>,
+}
+
+impl Catalog {
+    /// Registers a new database with its default `public` schema, then -- as part of the same
+    /// construction step, not as a separate opt-in call -- registers every [`SystemTableProvider`]
+    /// schema (`pg_catalog`, `information_schema`) into it. Without this, those providers are fully
+    /// implemented but unreachable: nothing ever adds their schema to a newly created database.
+    ///
+    /// [`SystemTableProvider`]: super::system_catalog::SystemTableProvider
+    pub fn create_database(&mut self, database_id: DatabaseId, name: String, owner: UserId) {
+        let mut database = DatabaseCatalog::new(database_id, name.clone());
+        database.create_schema(SchemaCatalog::new(
+            0,
+            DEFAULT_SCHEMA_NAME.to_string(),
+            owner,
+        ));
+
+        self.databases.insert(database_id, database);
+        self.database_ids_by_name.insert(name, database_id);
+
+        register_system_catalogs(self, database_id);
+    }
+
+    /// Creates `schema_name` (e.g. `pg_catalog`) under `database_id` and populates it with
+    /// `tables`, the way [`DatabaseCatalog::create_schema`] populates an ordinary user schema --
+    /// except the owner is irrelevant, since these schemas are read-only and owned by no one.
+    pub(crate) fn create_system_schema(
+        &mut self,
+        database_id: DatabaseId,
+        schema_name: &'static str,
+        tables: Vec<TableCatalog>,
+    ) {
+        let Some(database) = self.databases.get_mut(&database_id) else {
+            return;
+        };
+        let mut schema = SchemaCatalog::new(0, schema_name.to_string(), UserId::default());
+        for table in tables {
+            schema.create_table(table);
+        }
+        database.create_schema(schema);
+    }
+
+    pub fn iter_schemas(&self, database_name: &str) -> impl Iterator<Item = &SchemaCatalog> {
+        self.database_ids_by_name
+            .get(database_name)
+            .and_then(|id| self.databases.get(id))
+            .into_iter()
+            .flat_map(|database| database.iter_schemas())
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::catalog::table_catalog::TableCatalog;
+use crate::catalog::SchemaId;
+use crate::user::UserId;
+
+/// One schema within a database: a flat namespace of tables, views, sinks and indexes. Both
+/// ordinary user schemas (e.g. `public`) and the read-only schemas a [`super::system_catalog`]
+/// provider registers (e.g. `pg_catalog`) are represented the same way.
+pub struct SchemaCatalog {
+    id: SchemaId,
+    name: String,
+    owner: UserId,
+    tables: HashMap<String, TableCatalog>,
+}
+
+impl SchemaCatalog {
+    pub fn new(id: SchemaId, name: String, owner: UserId) -> Self {
+        Self {
+            id,
+            name,
+            owner,
+            tables: HashMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> SchemaId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> UserId {
+        self.owner
+    }
+
+    pub fn create_table(&mut self, table: TableCatalog) {
+        self.tables.insert(table.name().to_string(), table);
+    }
+
+    pub fn iter_all_tables(&self) -> impl Iterator<Item = &TableCatalog> {
+        self.tables.values()
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `information_schema` provider computed directly from [`Catalog`], so that
+//! `information_schema.tables` / `information_schema.columns` reflect `ALTER TABLE` as soon as
+//! [`crate::handler::alter_table_column::handle_alter_table_column`] commits the new definition,
+//! rather than from some snapshot taken at connection time.
+//!
+//! Registered the same way as [`super::pg_catalog`]: [`super::register_system_catalogs`] is the
+//! only caller of [`InformationSchemaProvider::tables`], invoked once per database by
+//! [`crate::catalog::root_catalog::Catalog::create_database`]. Without that call site this
+//! provider's rows are fully correct but unreachable, since nothing else ever adds
+//! `information_schema` to a database's schema namespace.
+
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::plan_common::column_desc::GeneratedOrDefaultColumn;
+
+use crate::catalog::root_catalog::Catalog;
+use crate::catalog::system_catalog::{SystemCatalogRow, SystemTable, SystemTableProvider};
+use crate::expr::ExprImpl;
+
+pub const INFORMATION_SCHEMA_SCHEMA_NAME: &str = "information_schema";
+
+pub struct InformationSchemaProvider;
+
+impl SystemTableProvider for InformationSchemaProvider {
+    fn schema_name(&self) -> &'static str {
+        INFORMATION_SCHEMA_SCHEMA_NAME
+    }
+
+    fn tables(&self) -> Vec<Box<dyn SystemTable>> {
+        vec![
+            Box::new(TablesTable),
+            Box::new(ColumnsTable),
+            Box::new(EnginesTable),
+        ]
+    }
+}
+
+/// `information_schema.tables` — one row per base table / view / materialized view, keyed by the
+/// same `(database, schema, table)` triple as `information_schema.columns` below.
+struct TablesTable;
+
+impl SystemTable for TablesTable {
+    fn name(&self) -> &'static str {
+        "tables"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("table_catalog", DataType::Varchar),
+            ("table_schema", DataType::Varchar),
+            ("table_name", DataType::Varchar),
+            ("table_type", DataType::Varchar),
+        ]
+    }
+
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow> {
+        let mut rows = vec![];
+        for schema in catalog.iter_schemas(database_name) {
+            for table in schema.iter_all_tables() {
+                rows.push(vec![
+                    Some(ScalarImpl::Utf8(database_name.into())),
+                    Some(ScalarImpl::Utf8(schema.name().into())),
+                    Some(ScalarImpl::Utf8(table.name().into())),
+                    Some(ScalarImpl::Utf8(table_type_str(table).into())),
+                ]);
+            }
+        }
+        rows.sort_by(|a, b| (&a[1], &a[2]).cmp(&(&b[1], &b[2])));
+        rows
+    }
+}
+
+/// `information_schema.columns` — ordered by `(table_id, column_id)` so that repeated reads
+/// without an intervening `ALTER TABLE` are stable, and `ordinal_position` is always the column's
+/// current 1-based index in the live definition.
+///
+/// `column_default` is recovered from the same `GeneratedOrDefaultColumn::DefaultColumn` expr that
+/// `replace_table_with_definition` parses out of the new `CREATE TABLE` AST when building the
+/// default-value projection for incoming sinks.
+struct ColumnsTable;
+
+impl SystemTable for ColumnsTable {
+    fn name(&self) -> &'static str {
+        "columns"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("table_catalog", DataType::Varchar),
+            ("table_schema", DataType::Varchar),
+            ("table_name", DataType::Varchar),
+            ("column_name", DataType::Varchar),
+            ("ordinal_position", DataType::Int32),
+            ("is_nullable", DataType::Varchar),
+            ("data_type", DataType::Varchar),
+            ("column_default", DataType::Varchar),
+        ]
+    }
+
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow> {
+        let mut rows = vec![];
+        for schema in catalog.iter_schemas(database_name) {
+            for table in schema.iter_all_tables() {
+                for (ordinal, column) in table.columns().iter().enumerate() {
+                    let key = (table.id().table_id(), column.column_desc.column_id.get_id());
+                    rows.push((key, vec![
+                        Some(ScalarImpl::Utf8(database_name.into())),
+                        Some(ScalarImpl::Utf8(schema.name().into())),
+                        Some(ScalarImpl::Utf8(table.name().into())),
+                        Some(ScalarImpl::Utf8(column.name().into())),
+                        Some(ScalarImpl::Int32((ordinal + 1) as i32)),
+                        Some(ScalarImpl::Utf8(
+                            if column.is_nullable() { "YES" } else { "NO" }.into(),
+                        )),
+                        Some(ScalarImpl::Utf8(
+                            format!("{}", column.data_type()).into(),
+                        )),
+                        column_default(column.column_desc.generated_or_default_column.as_ref())
+                            .map(|s| ScalarImpl::Utf8(s.into())),
+                    ]));
+                }
+            }
+        }
+        // Ordered by (table id, column id) rather than display name, matching the ids the alter
+        // path hands out via `ColumnIdGenerator`.
+        rows.sort_by_key(|(key, _)| *key);
+        rows.into_iter().map(|(_, row)| row).collect()
+    }
+}
+
+/// `information_schema.engines` — a single constant row describing the storage engine, mirroring
+/// MySQL's `information_schema.engines` so tools that probe it before introspecting further don't
+/// bail out early.
+struct EnginesTable;
+
+impl SystemTable for EnginesTable {
+    fn name(&self) -> &'static str {
+        "engines"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("engine", DataType::Varchar),
+            ("support", DataType::Varchar),
+            ("comment", DataType::Varchar),
+        ]
+    }
+
+    fn rows(&self, _catalog: &Catalog, _database_name: &str) -> Vec<SystemCatalogRow> {
+        vec![vec![
+            Some(ScalarImpl::Utf8("Hummock".into())),
+            Some(ScalarImpl::Utf8("DEFAULT".into())),
+            Some(ScalarImpl::Utf8(
+                "RisingWave's LSM-tree based cloud-native streaming state store".into(),
+            )),
+        ]]
+    }
+}
+
+fn table_type_str(table: &crate::catalog::table_catalog::TableCatalog) -> &'static str {
+    use crate::catalog::table_catalog::TableType;
+    match table.table_type() {
+        TableType::Table => "BASE TABLE",
+        TableType::MaterializedView => "MATERIALIZED VIEW",
+        TableType::Index => "INDEX",
+        TableType::Internal => "BASE TABLE",
+    }
+}
+
+/// Renders a column's default expression back to SQL text for `column_default`, the same way
+/// `\d` expects to see it. Generated columns and plain columns without a default report `NULL`.
+fn column_default(generated_or_default: Option<&GeneratedOrDefaultColumn>) -> Option<String> {
+    match generated_or_default {
+        Some(GeneratedOrDefaultColumn::DefaultColumn(default_col)) => default_col
+            .expr
+            .as_ref()
+            .and_then(|expr| ExprImpl::from_expr_proto(expr).ok())
+            .map(|expr| expr.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! System catalogs that are computed on demand from the live [`root_catalog::Catalog`], rather
+//! than being persisted like ordinary relations.
+//!
+//! Unlike user tables, a system catalog has no backing storage: every query against it re-derives
+//! its rows from whatever is currently registered in the in-memory catalog. This means DDL (e.g.
+//! `ALTER TABLE ... ADD/DROP COLUMN`) is visible to readers of these catalogs immediately, with no
+//! extra synchronization, since they read through the same [`crate::catalog::root_catalog::Catalog`]
+//! that DDL handlers mutate.
+
+pub mod information_schema;
+pub mod pg_catalog;
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::root_catalog::Catalog;
+use crate::catalog::table_catalog::TableCatalog;
+use crate::catalog::DatabaseId;
+
+/// A single virtual row materialized for a system catalog table.
+pub(crate) type SystemCatalogRow = Vec<risingwave_common::types::Datum>;
+
+/// A virtual table whose rows are computed on demand from the live catalog.
+///
+/// Implementors back one entry of a [`SystemTableProvider`] (e.g. `pg_class`,
+/// `information_schema.columns`): [`Self::columns`] describes the fixed schema returned to
+/// clients, and [`Self::rows`] recomputes the table contents from `catalog` every time it is
+/// read.
+pub(crate) trait SystemTable {
+    /// The unqualified name of this virtual table, e.g. `"pg_class"`.
+    fn name(&self) -> &'static str;
+
+    /// The fixed column layout of this virtual table.
+    fn columns(&self) -> &[(&'static str, DataType)];
+
+    /// Recompute all rows of this virtual table from the current state of `catalog`.
+    ///
+    /// Implementations should keep row order stable (e.g. by table/column id) so that repeated
+    /// reads without intervening DDL return identical results.
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow>;
+}
+
+/// A named collection of [`SystemTable`]s exposed as a read-only schema (e.g. `pg_catalog`,
+/// `information_schema`).
+pub(crate) trait SystemTableProvider {
+    /// The name of the schema this provider registers into the root catalog, e.g.
+    /// `PG_CATALOG_SCHEMA_NAME`.
+    fn schema_name(&self) -> &'static str;
+
+    /// All virtual tables this provider exposes, each identified by its unqualified
+    /// [`SystemTable::name`].
+    fn tables(&self) -> Vec<Box<dyn SystemTable>>;
+}
+
+/// Builds a dummy, storage-less [`TableCatalog`] that only carries the column layout of a
+/// [`SystemTable`], for registration into a schema so that binder/planner lookups (and
+/// `SchemaPath` resolution) succeed as if it were an ordinary relation.
+pub(crate) fn system_table_catalog(table: &dyn SystemTable, id: u32) -> TableCatalog {
+    use risingwave_common::catalog::ColumnDesc;
+
+    let columns = table
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, (name, data_type))| {
+            risingwave_common::catalog::ColumnCatalog {
+                column_desc: ColumnDesc::named(*name, (i as i32).into(), data_type.clone()),
+                is_hidden: false,
+            }
+        })
+        .collect();
+
+    TableCatalog {
+        id: id.into(),
+        name: table.name().to_string(),
+        columns,
+        ..TableCatalog::default_system_table()
+    }
+}
+
+/// Every [`SystemTableProvider`] that [`register_system_catalogs`] registers into a newly created
+/// database.
+pub(crate) fn system_table_providers() -> Vec<Box<dyn SystemTableProvider>> {
+    vec![
+        Box::new(pg_catalog::PgCatalogProvider),
+        Box::new(information_schema::InformationSchemaProvider),
+    ]
+}
+
+/// Registers every [`system_table_providers`] schema (and the virtual tables it exposes) into
+/// `catalog` for `database_id`, the same way a newly created database gets its default `public`
+/// schema registered.
+///
+/// Without this, a [`SystemTableProvider`] is fully implemented but unreachable: nothing ever adds
+/// its schema to the catalog, so `SchemaPath`/binder lookups against e.g. `pg_catalog.pg_class`
+/// would fail to resolve.
+pub(crate) fn register_system_catalogs(catalog: &mut Catalog, database_id: DatabaseId) {
+    for provider in system_table_providers() {
+        let tables = provider
+            .tables()
+            .iter()
+            .enumerate()
+            .map(|(i, table)| system_table_catalog(table.as_ref(), i as u32))
+            .collect();
+        catalog.create_system_schema(database_id, provider.schema_name(), tables);
+    }
+}
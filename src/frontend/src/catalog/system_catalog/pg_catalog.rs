@@ -0,0 +1,239 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A PostgreSQL-compatible `pg_catalog` schema, so that `psql` meta-commands (`\d`, `\dt`, ...)
+//! and BI tools that introspect `pg_namespace` / `pg_class` / `pg_attribute` / `pg_type` keep
+//! working against RisingWave. Every table here is virtual: rows are derived from
+//! [`crate::catalog::root_catalog::Catalog`] on each read, so they reflect DDL (e.g.
+//! `ALTER TABLE ... ADD/DROP COLUMN`) as soon as it commits, with no separate refresh step.
+
+use risingwave_common::catalog::PG_CATALOG_SCHEMA_NAME;
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use crate::catalog::root_catalog::Catalog;
+use crate::catalog::system_catalog::{SystemCatalogRow, SystemTable, SystemTableProvider};
+
+pub struct PgCatalogProvider;
+
+impl SystemTableProvider for PgCatalogProvider {
+    fn schema_name(&self) -> &'static str {
+        PG_CATALOG_SCHEMA_NAME
+    }
+
+    fn tables(&self) -> Vec<Box<dyn SystemTable>> {
+        vec![
+            Box::new(PgNamespaceTable),
+            Box::new(PgClassTable),
+            Box::new(PgAttributeTable),
+            Box::new(PgTypeTable),
+        ]
+    }
+}
+
+/// `pg_namespace` — one row per schema of the queried database.
+///
+/// RisingWave's catalog is two levels (database -> schema -> relation) while PostgreSQL's is one
+/// level (schema -> relation); we map each RisingWave schema to a `pg_namespace` row so that
+/// `SchemaPath`/`search_path` based lookups against `pg_namespace` resolve the way `psql` expects.
+struct PgNamespaceTable;
+
+impl SystemTable for PgNamespaceTable {
+    fn name(&self) -> &'static str {
+        "pg_namespace"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("oid", DataType::Int32),
+            ("nspname", DataType::Varchar),
+            ("nspowner", DataType::Int32),
+        ]
+    }
+
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow> {
+        catalog
+            .iter_schemas(database_name)
+            .map(|schema| {
+                vec![
+                    Some(ScalarImpl::Int32(schema.id() as i32)),
+                    Some(ScalarImpl::Utf8(schema.name().into())),
+                    Some(ScalarImpl::Int32(schema.owner() as i32)),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// `pg_class` — one row per table, source, materialized view or index, keyed by the same id
+/// RisingWave already uses internally so joins against `pg_attribute.attrelid` are trivial.
+struct PgClassTable;
+
+impl SystemTable for PgClassTable {
+    fn name(&self) -> &'static str {
+        "pg_class"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("oid", DataType::Int32),
+            ("relname", DataType::Varchar),
+            ("relnamespace", DataType::Int32),
+            ("relkind", DataType::Varchar),
+            ("relowner", DataType::Int32),
+        ]
+    }
+
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow> {
+        let mut rows = vec![];
+        for schema in catalog.iter_schemas(database_name) {
+            for table in schema.iter_all_tables() {
+                rows.push(vec![
+                    Some(ScalarImpl::Int32(table.id().table_id() as i32)),
+                    Some(ScalarImpl::Utf8(table.name().into())),
+                    Some(ScalarImpl::Int32(schema.id() as i32)),
+                    Some(ScalarImpl::Utf8(relkind_of(table).into())),
+                    Some(ScalarImpl::Int32(table.owner() as i32)),
+                ]);
+            }
+        }
+        // Stable order for clients doing plain `SELECT * FROM pg_class`.
+        rows.sort_by(|a, b| a[0].cmp(&b[0]));
+        rows
+    }
+}
+
+/// `pg_attribute` — one row per column of every relation in `pg_class`, with `attnum` starting at
+/// 1 and `atttypid` resolved through [`pg_type_oid`] so that clients can join straight through to
+/// `pg_type`.
+struct PgAttributeTable;
+
+impl SystemTable for PgAttributeTable {
+    fn name(&self) -> &'static str {
+        "pg_attribute"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[
+            ("attrelid", DataType::Int32),
+            ("attname", DataType::Varchar),
+            ("atttypid", DataType::Int32),
+            ("attnum", DataType::Int16),
+            ("attnotnull", DataType::Boolean),
+        ]
+    }
+
+    fn rows(&self, catalog: &Catalog, database_name: &str) -> Vec<SystemCatalogRow> {
+        let mut rows = vec![];
+        for schema in catalog.iter_schemas(database_name) {
+            for table in schema.iter_all_tables() {
+                for (attnum, column) in table.columns().iter().enumerate() {
+                    rows.push(vec![
+                        Some(ScalarImpl::Int32(table.id().table_id() as i32)),
+                        Some(ScalarImpl::Utf8(column.name().into())),
+                        Some(ScalarImpl::Int32(pg_type_oid(column.data_type()))),
+                        Some(ScalarImpl::Int16((attnum + 1) as i16)),
+                        Some(ScalarImpl::Bool(!column.is_nullable())),
+                    ]);
+                }
+            }
+        }
+        rows
+    }
+}
+
+/// `pg_type` — the fixed set of builtin type oids RisingWave's [`DataType`] maps onto. This table
+/// does not depend on the catalog: it is the same closed set of types for every database.
+struct PgTypeTable;
+
+impl SystemTable for PgTypeTable {
+    fn name(&self) -> &'static str {
+        "pg_type"
+    }
+
+    fn columns(&self) -> &[(&'static str, DataType)] {
+        &[("oid", DataType::Int32), ("typname", DataType::Varchar)]
+    }
+
+    fn rows(&self, _catalog: &Catalog, _database_name: &str) -> Vec<SystemCatalogRow> {
+        PG_TYPE_NAMES
+            .iter()
+            .map(|(oid, name)| {
+                vec![
+                    Some(ScalarImpl::Int32(*oid)),
+                    Some(ScalarImpl::Utf8((*name).into())),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// `relkind` as PostgreSQL's `pg_class.relkind` encodes it: `r` for ordinary table, `v` for view,
+/// `m` for materialized view, `i` for index, `S` for sequence.
+fn relkind_of(table: &crate::catalog::table_catalog::TableCatalog) -> &'static str {
+    use crate::catalog::table_catalog::TableType;
+    match table.table_type() {
+        TableType::Table => "r",
+        TableType::MaterializedView => "m",
+        TableType::Index => "i",
+        TableType::Internal => "r",
+    }
+}
+
+/// The well-known PostgreSQL oids for the types RisingWave supports, i.e. the values
+/// [`pg_type_oid`] reports for `pg_attribute.atttypid`, enumerated wholesale by `pg_type`.
+const PG_TYPE_NAMES: &[(i32, &str)] = &[
+    (16, "bool"),
+    (21, "int2"),
+    (23, "int4"),
+    (20, "int8"),
+    (700, "float4"),
+    (701, "float8"),
+    (1700, "numeric"),
+    (1043, "varchar"),
+    (17, "bytea"),
+    (1082, "date"),
+    (1083, "time"),
+    (1114, "timestamp"),
+    (1184, "timestamptz"),
+    (1186, "interval"),
+    (3802, "jsonb"),
+    (2950, "uuid"),
+    (2249, "record"),
+];
+
+/// Maps a RisingWave [`DataType`] to the oid `pg_type` would report for it. A `List` reports its
+/// element type's oid; a `Struct` reports the oid of `record`.
+fn pg_type_oid(data_type: &DataType) -> i32 {
+    match data_type {
+        DataType::Boolean => 16,
+        DataType::Int16 => 21,
+        DataType::Int32 => 23,
+        DataType::Int64 => 20,
+        DataType::Int256 => 1700,
+        DataType::Float32 => 700,
+        DataType::Float64 => 701,
+        DataType::Decimal => 1700,
+        DataType::Varchar => 1043,
+        DataType::Bytea => 17,
+        DataType::Date => 1082,
+        DataType::Time => 1083,
+        DataType::Timestamp => 1114,
+        DataType::Timestamptz => 1184,
+        DataType::Interval => 1186,
+        DataType::Jsonb => 3802,
+        DataType::Serial => 23,
+        DataType::Struct(_) => 2249,  // pg_type oid for `record`
+        DataType::List(inner) => pg_type_oid(inner),
+    }
+}
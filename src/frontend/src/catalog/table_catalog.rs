@@ -0,0 +1,76 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::catalog::ColumnCatalog;
+
+use crate::catalog::{RelationCatalog, TableId};
+use crate::user::UserId;
+
+/// What kind of relation a [`TableCatalog`] backs, distinguishing the handful of things this
+/// crate currently represents as a table-shaped catalog entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableType {
+    Table,
+    MaterializedView,
+    Index,
+    /// A storage-less relation with no backing state, e.g. a system catalog's virtual table.
+    Internal,
+}
+
+/// A table, materialized view, or index as tracked by the catalog.
+#[derive(Debug, Clone)]
+pub struct TableCatalog {
+    pub id: TableId,
+    pub name: String,
+    pub columns: Vec<ColumnCatalog>,
+    pub owner: UserId,
+    pub table_type: TableType,
+}
+
+impl TableCatalog {
+    pub fn id(&self) -> TableId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn columns(&self) -> &[ColumnCatalog] {
+        &self.columns
+    }
+
+    pub fn table_type(&self) -> TableType {
+        self.table_type
+    }
+
+    /// A storage-less placeholder carrying no columns of its own, meant to be overridden via
+    /// struct-update syntax (e.g. [`super::system_catalog::system_table_catalog`] fills in `id`,
+    /// `name` and `columns` for a virtual system table).
+    pub fn default_system_table() -> Self {
+        Self {
+            id: TableId::placeholder(),
+            name: String::new(),
+            columns: vec![],
+            owner: UserId::default(),
+            table_type: TableType::Internal,
+        }
+    }
+}
+
+impl RelationCatalog for TableCatalog {
+    fn owner(&self) -> UserId {
+        self.owner
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::anyhow;
 use risingwave_hummock_sdk::time_travel::{
-    refill_version, IncompleteHummockVersion, IncompleteHummockVersionDelta,
+    checksum_sstable_info, refill_version, IncompleteHummockVersion, IncompleteHummockVersionDelta,
 };
 use risingwave_hummock_sdk::version::{HummockVersion, HummockVersionDelta};
 use risingwave_hummock_sdk::{HummockEpoch, HummockSstableId, HummockSstableObjectId};
@@ -14,8 +16,8 @@ use risingwave_pb::hummock::{PbHummockVersion, PbHummockVersionDelta, PbSstableI
 use sea_orm::sea_query::OnConflict;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
-    ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    TransactionTrait,
+    ColumnTrait, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
 };
 
 use crate::controller::SqlMetaStore;
@@ -23,6 +25,64 @@ use crate::hummock::error::{Error, Result};
 use crate::hummock::HummockManager;
 use crate::manager::MetaStoreImpl;
 
+/// A Hummock epoch packs physical unix-millis time into its high bits, leaving the low bits for a
+/// logical counter within the same millisecond. Mirrors the encoding used when epochs are
+/// generated, so a timestamp can be converted into the epoch that was current at that instant.
+const EPOCH_PHYSICAL_SHIFT: u32 = 16;
+
+/// The currently retained window of the time travel archive, as reported by
+/// [`HummockManager::list_time_travel_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeTravelRange {
+    pub oldest_epoch: HummockEpoch,
+    pub newest_epoch: HummockEpoch,
+    pub retained_version_count: u64,
+    pub retained_delta_count: u64,
+}
+
+/// A declarative retention policy for the time travel archive: keep everything newer than `now -
+/// retention_ms`, and/or keep at most `max_versions` most-recent versions. When both are set, the
+/// stricter (more aggressive) cutoff wins, the same way a bucket lifecycle rule combines an age
+/// rule with a version-count rule. Configured process-wide via
+/// [`HummockManager::set_time_travel_retention`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeTravelRetentionPolicy {
+    pub retention_ms: Option<u64>,
+    pub max_versions: Option<u64>,
+}
+
+/// Interval at which [`HummockManager::start_time_travel_retention_worker`] re-derives the epoch
+/// watermark from the configured [`TimeTravelRetentionPolicy`] and truncates the archive.
+const TIME_TRAVEL_RETENTION_WORKER_INTERVAL: Duration = Duration::from_secs(60);
+
+fn time_travel_retention_policy_store() -> &'static RwLock<TimeTravelRetentionPolicy> {
+    static POLICY: OnceLock<RwLock<TimeTravelRetentionPolicy>> = OnceLock::new();
+    POLICY.get_or_init(|| RwLock::new(TimeTravelRetentionPolicy::default()))
+}
+
+/// A point-in-time accounting of the time-travel archive's pinned object-storage footprint, as
+/// reported by [`HummockManager::time_travel_stats`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeTravelStats {
+    pub retained_sst_count: u64,
+    pub retained_sst_bytes: u64,
+}
+
+/// A soft quota on the time-travel archive's pinned footprint. Exceeding either bound makes
+/// [`HummockManager::enforce_time_travel_quota`] advance the truncation watermark aggressively,
+/// independent of the age/count-based [`TimeTravelRetentionPolicy`], to bring the archive back
+/// under budget.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeTravelQuota {
+    pub max_sst_count: Option<u64>,
+    pub max_sst_bytes: Option<u64>,
+}
+
+fn time_travel_quota_store() -> &'static RwLock<TimeTravelQuota> {
+    static QUOTA: OnceLock<RwLock<TimeTravelQuota>> = OnceLock::new();
+    QUOTA.get_or_init(|| RwLock::new(TimeTravelQuota::default()))
+}
+
 /// Time travel.
 impl HummockManager {
     pub(crate) fn sql_store(&self) -> Result<SqlMetaStore> {
@@ -170,6 +230,9 @@ impl HummockManager {
             next_version_sst_ids = sst_ids;
         }
 
+        // Strictly `lt`, never `lte`: `earliest_valid_version_id` itself must stay, since it's the
+        // checkpoint that the next `epoch_to_version` replay (and the next forced checkpoint in
+        // `maybe_checkpoint_time_travel_version`) anchors to.
         let res = hummock_time_travel_version::Entity::delete_many()
             .filter(hummock_time_travel_version::Column::VersionId.lt(earliest_valid_version_id))
             .exec(&txn)
@@ -196,6 +259,228 @@ impl HummockManager {
         Ok(())
     }
 
+    /// Reports the currently queryable window of the time travel archive, so operators and
+    /// callers can tell what's retained before issuing a point-in-time query. Mirrors the
+    /// information Delta Lake's `DESCRIBE HISTORY` surfaces about its own retained window.
+    pub async fn list_time_travel_range(&self) -> crate::hummock::error::Result<TimeTravelRange> {
+        let sql_store = self.sql_store()?;
+
+        let oldest_epoch: Option<risingwave_meta_model_v2::Epoch> =
+            hummock_epoch_to_version::Entity::find()
+                .select_only()
+                .column(hummock_epoch_to_version::Column::Epoch)
+                .order_by_asc(hummock_epoch_to_version::Column::Epoch)
+                .into_tuple()
+                .one(&sql_store.conn)
+                .await?;
+        let newest_epoch: Option<risingwave_meta_model_v2::Epoch> =
+            hummock_epoch_to_version::Entity::find()
+                .select_only()
+                .column(hummock_epoch_to_version::Column::Epoch)
+                .order_by_desc(hummock_epoch_to_version::Column::Epoch)
+                .into_tuple()
+                .one(&sql_store.conn)
+                .await?;
+        let retained_version_count = hummock_time_travel_version::Entity::find()
+            .count(&sql_store.conn)
+            .await?;
+        let retained_delta_count = hummock_time_travel_delta::Entity::find()
+            .count(&sql_store.conn)
+            .await?;
+
+        let (oldest_epoch, newest_epoch) = match (oldest_epoch, newest_epoch) {
+            (Some(oldest), Some(newest)) => (
+                HummockEpoch::try_from(oldest).unwrap(),
+                HummockEpoch::try_from(newest).unwrap(),
+            ),
+            _ => (0, 0),
+        };
+
+        Ok(TimeTravelRange {
+            oldest_epoch,
+            newest_epoch,
+            retained_version_count,
+            retained_delta_count,
+        })
+    }
+
+    /// Lists `(epoch, version_id, max_committed_epoch)` entries of the time travel archive in
+    /// descending epoch order, at most `limit` at a time. Pass the `epoch` of the last row of a
+    /// previous page as `before_epoch` to continue. Since `hummock_epoch_to_version.epoch` is
+    /// always written as the committing delta's `max_committed_epoch` (see
+    /// [`Self::write_time_travel_metadata`]), the two coincide for every retained entry.
+    pub async fn list_time_travel_versions(
+        &self,
+        limit: u64,
+        before_epoch: Option<HummockEpoch>,
+    ) -> crate::hummock::error::Result<Vec<(HummockEpoch, u64, HummockEpoch)>> {
+        let sql_store = self.sql_store()?;
+        let mut query = hummock_epoch_to_version::Entity::find();
+        if let Some(before_epoch) = before_epoch {
+            query = query.filter(
+                hummock_epoch_to_version::Column::Epoch
+                    .lt(risingwave_meta_model_v2::Epoch::try_from(before_epoch).unwrap()),
+            );
+        }
+        let rows = query
+            .order_by_desc(hummock_epoch_to_version::Column::Epoch)
+            .limit(limit)
+            .all(&sql_store.conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let epoch = HummockEpoch::try_from(row.epoch).unwrap();
+                (epoch, row.version_id as u64, epoch)
+            })
+            .collect())
+    }
+
+    /// Replaces the process-wide [`TimeTravelRetentionPolicy`], taking effect on the next tick of
+    /// [`Self::start_time_travel_retention_worker`].
+    pub fn set_time_travel_retention(&self, policy: TimeTravelRetentionPolicy) {
+        *time_travel_retention_policy_store().write().unwrap() = policy;
+    }
+
+    pub fn time_travel_retention_policy(&self) -> TimeTravelRetentionPolicy {
+        *time_travel_retention_policy_store().read().unwrap()
+    }
+
+    /// Derives the epoch watermark implied by the current [`TimeTravelRetentionPolicy`] and
+    /// invokes [`Self::truncate_time_travel_metadata`] with it. A no-op if no policy has been
+    /// configured.
+    pub async fn apply_time_travel_retention_policy(&self) -> Result<()> {
+        let policy = self.time_travel_retention_policy();
+        if policy.retention_ms.is_none() && policy.max_versions.is_none() {
+            return Ok(());
+        }
+
+        let duration_watermark = policy.retention_ms.map(|retention_ms| {
+            let now_millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64;
+            let cutoff_millis = now_millis.saturating_sub(retention_ms);
+            (cutoff_millis as HummockEpoch) << EPOCH_PHYSICAL_SHIFT
+        });
+
+        let count_watermark = if let Some(max_versions) = policy.max_versions {
+            let sql_store = self.sql_store()?;
+            // The `max_versions`-th newest row (0-indexed offset `max_versions - 1`) is the oldest
+            // one still within budget; anything strictly older than it is truncated.
+            let cutoff_row = hummock_epoch_to_version::Entity::find()
+                .order_by_desc(hummock_epoch_to_version::Column::Epoch)
+                .offset(max_versions.saturating_sub(1))
+                .one(&sql_store.conn)
+                .await?;
+            cutoff_row.map(|row| HummockEpoch::try_from(row.epoch).unwrap())
+        } else {
+            None
+        };
+
+        // The stricter policy keeps fewer rows, i.e. has the larger (more recent) cutoff epoch.
+        let watermark = match (duration_watermark, count_watermark) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (Some(a), None) | (None, Some(a)) => Some(a),
+            (None, None) => None,
+        };
+        let Some(watermark) = watermark else {
+            return Ok(());
+        };
+
+        self.truncate_time_travel_metadata(watermark).await
+    }
+
+    /// Spawns a background task that re-derives and applies the [`TimeTravelRetentionPolicy`]
+    /// every [`TIME_TRAVEL_RETENTION_WORKER_INTERVAL`], turning manual watermark management into
+    /// a policy operators declare once via [`Self::set_time_travel_retention`].
+    pub fn start_time_travel_retention_worker(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(TIME_TRAVEL_RETENTION_WORKER_INTERVAL);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.apply_time_travel_retention_policy().await {
+                    tracing::warn!(error = ?e, "failed to apply time travel retention policy");
+                }
+                if let Err(e) = self.enforce_time_travel_quota().await {
+                    tracing::warn!(error = ?e, "failed to enforce time travel quota");
+                }
+            }
+        })
+    }
+
+    /// Replaces the process-wide [`TimeTravelQuota`], taking effect on the next tick of
+    /// [`Self::start_time_travel_retention_worker`].
+    pub fn set_time_travel_quota(&self, quota: TimeTravelQuota) {
+        *time_travel_quota_store().write().unwrap() = quota;
+    }
+
+    pub fn time_travel_quota(&self) -> TimeTravelQuota {
+        *time_travel_quota_store().read().unwrap()
+    }
+
+    /// Computes the archive's currently retained footprint from `hummock_sstable_info`. Object
+    /// size isn't broken out into its own column, so this deserializes every retained row's
+    /// embedded `SstableInfo` rather than maintaining an in-memory counter incrementally --
+    /// avoiding a counter that would silently read as zero (and never self-correct) after every
+    /// meta node restart. Acceptable at the rate this is called (once per retention worker tick);
+    /// would be worth promoting `file_size` to a dedicated column if the archive grows huge.
+    pub async fn time_travel_stats(&self) -> Result<TimeTravelStats> {
+        let sql_store = self.sql_store()?;
+        let rows = hummock_sstable_info::Entity::find()
+            .all(&sql_store.conn)
+            .await?;
+        let retained_sst_count = rows.len() as u64;
+        let retained_sst_bytes = rows
+            .iter()
+            .map(|row| row.sstable_info.to_protobuf().file_size)
+            .sum();
+        Ok(TimeTravelStats {
+            retained_sst_count,
+            retained_sst_bytes,
+        })
+    }
+
+    /// If the configured [`TimeTravelQuota`] is exceeded, truncates roughly half of the currently
+    /// retained `hummock_epoch_to_version` entries (oldest first). Converges back under budget
+    /// over a few ticks of [`Self::start_time_travel_retention_worker`] rather than computing an
+    /// exact cutoff in one shot.
+    pub async fn enforce_time_travel_quota(&self) -> Result<()> {
+        let quota = self.time_travel_quota();
+        if quota.max_sst_count.is_none() && quota.max_sst_bytes.is_none() {
+            return Ok(());
+        }
+        let stats = self.time_travel_stats().await?;
+        let over_budget = quota.max_sst_count.is_some_and(|max| stats.retained_sst_count > max)
+            || quota.max_sst_bytes.is_some_and(|max| stats.retained_sst_bytes > max);
+        if !over_budget {
+            return Ok(());
+        }
+
+        let sql_store = self.sql_store()?;
+        let total_versions = hummock_epoch_to_version::Entity::find()
+            .count(&sql_store.conn)
+            .await?;
+        let keep = total_versions / 2;
+        let Some(cutoff_row) = hummock_epoch_to_version::Entity::find()
+            .order_by_desc(hummock_epoch_to_version::Column::Epoch)
+            .offset(keep)
+            .one(&sql_store.conn)
+            .await?
+        else {
+            return Ok(());
+        };
+        let watermark = HummockEpoch::try_from(cutoff_row.epoch).unwrap();
+        tracing::warn!(
+            retained_sst_count = stats.retained_sst_count,
+            retained_sst_bytes = stats.retained_sst_bytes,
+            watermark,
+            "time travel archive exceeds its configured quota; truncating aggressively"
+        );
+        self.truncate_time_travel_metadata(watermark).await
+    }
+
     pub(crate) async fn all_object_ids_in_time_travel(
         &self,
     ) -> crate::hummock::error::Result<impl Iterator<Item = HummockSstableId>> {
@@ -289,21 +574,85 @@ impl HummockManager {
                 query_epoch, expected_version_id,
             ))));
         }
-        refill_version(&mut actual_version, &sst_id_to_info);
+        refill_version(&mut actual_version, &sst_id_to_info)
+            .map_err(|e| Error::TimeTravel(anyhow!(e)))?;
         Ok(actual_version)
     }
 
+    /// Like [`Self::epoch_to_version`], but takes a wall-clock `unix_millis` timestamp instead of
+    /// a raw [`HummockEpoch`], so that SQL-level `FOR SYSTEM_TIME AS OF TIMESTAMP` queries don't
+    /// need to hand-roll epoch arithmetic. `unix_millis` is converted into the epoch that was
+    /// current at that instant and then resolved the same way `epoch_to_version` does.
+    ///
+    /// Returns a distinct "timestamp before retention window" error, rather than the generic
+    /// "version not found", when `unix_millis` predates everything retained in
+    /// `hummock_epoch_to_version` or the archive is empty, since retention is an operator-tunable
+    /// setting. A timestamp newer than the newest retained epoch resolves to the newest retained
+    /// version.
+    pub async fn timestamp_to_version(
+        &self,
+        unix_millis: u64,
+    ) -> crate::hummock::error::Result<HummockVersion> {
+        let sql_store = self.sql_store()?;
+        let query_epoch: HummockEpoch = unix_millis << EPOCH_PHYSICAL_SHIFT;
+
+        let earliest_epoch: Option<risingwave_meta_model_v2::Epoch> =
+            hummock_epoch_to_version::Entity::find()
+                .select_only()
+                .column(hummock_epoch_to_version::Column::Epoch)
+                .order_by_asc(hummock_epoch_to_version::Column::Epoch)
+                .into_tuple()
+                .one(&sql_store.conn)
+                .await?;
+        let Some(earliest_epoch) = earliest_epoch else {
+            return Err(Error::TimeTravel(anyhow!(
+                "timestamp before retention window: time travel archive is currently empty"
+            )));
+        };
+        if risingwave_meta_model_v2::Epoch::try_from(query_epoch).unwrap() < earliest_epoch {
+            return Err(Error::TimeTravel(anyhow!(format!(
+                "timestamp before retention window: {unix_millis} (epoch {query_epoch}) predates the earliest retained epoch {earliest_epoch}",
+            ))));
+        }
+
+        self.epoch_to_version(query_epoch).await
+    }
+
     pub(crate) async fn write_time_travel_metadata(
         &self,
         txn: &DatabaseTransaction,
         version: Option<&HummockVersion>,
         delta: HummockVersionDelta,
     ) -> Result<()> {
+        // `hummock_sstable_info` rows are first-write-wins per `sst_id` (the `on_conflict`
+        // below), so if `sst_id` were ever recycled for a different SST, whichever version was
+        // stripped first would silently win and every later version referencing that `sst_id`
+        // would be refilled with the wrong object. Check the existing row's checksum before
+        // trusting the `do_nothing`, and fail loudly instead of corrupting a later time-travel
+        // read.
         async fn write_sstable_infos(
             sst_infos: impl Iterator<Item = &PbSstableInfo>,
             txn: &DatabaseTransaction,
         ) -> Result<()> {
             for sst_info in sst_infos {
+                if let Some(existing) = hummock_sstable_info::Entity::find()
+                    .filter(
+                        hummock_sstable_info::Column::SstId
+                            .eq(i64::try_from(sst_info.sst_id).unwrap()),
+                    )
+                    .one(txn)
+                    .await?
+                {
+                    let existing_info = existing.sstable_info.to_protobuf();
+                    if checksum_sstable_info(&existing_info) != checksum_sstable_info(sst_info) {
+                        return Err(Error::TimeTravel(anyhow!(format!(
+                            "sst_id {} is already recorded with a different SstableInfo (object_id {} vs {}); sst_id reuse is not supported",
+                            sst_info.sst_id, existing_info.object_id, sst_info.object_id,
+                        ))));
+                    }
+                    continue;
+                }
+
                 let m = hummock_sstable_info::ActiveModel {
                     sst_id: Set(sst_info.sst_id.try_into().unwrap()),
                     object_id: Set(sst_info.object_id.try_into().unwrap()),
@@ -332,6 +681,7 @@ impl HummockManager {
             .exec(txn)
             .await?;
 
+        let mut checkpointed = false;
         if let Some(version) = version {
             write_sstable_infos(version.get_sst_infos(), &txn).await?;
             let m = hummock_time_travel_version::ActiveModel {
@@ -349,6 +699,7 @@ impl HummockManager {
                 .do_nothing()
                 .exec(txn)
                 .await?;
+            checkpointed = true;
         }
         write_sstable_infos(delta.newly_added_sst_infos(), txn).await?;
         let m = hummock_time_travel_delta::ActiveModel {
@@ -367,8 +718,90 @@ impl HummockManager {
             .exec(txn)
             .await?;
 
+        if !checkpointed {
+            self.maybe_checkpoint_time_travel_version(txn, delta.id).await?;
+        }
+
         Ok(())
     }
+
+    /// Forces a full `hummock_time_travel_version` checkpoint at `up_to_version_id` once at least
+    /// [`time_travel_checkpoint_interval`] deltas have accumulated since the last one, so that
+    /// [`Self::epoch_to_version`]'s call to `replay_archive` never has to fold over more than one
+    /// interval's worth of deltas. Only called when the caller of `write_time_travel_metadata`
+    /// didn't already supply a full `version` for this delta.
+    async fn maybe_checkpoint_time_travel_version(
+        &self,
+        txn: &DatabaseTransaction,
+        up_to_version_id: u64,
+    ) -> Result<()> {
+        let Some(last_checkpoint) = hummock_time_travel_version::Entity::find()
+            .order_by_desc(hummock_time_travel_version::Column::VersionId)
+            .one(txn)
+            .await?
+        else {
+            // No checkpoint exists yet. The very first delta is expected to have been written
+            // together with a full `version`, so there's nothing to anchor a checkpoint to here.
+            return Ok(());
+        };
+        let checkpoint_version_id = last_checkpoint.version_id;
+
+        let pending_deltas = hummock_time_travel_delta::Entity::find()
+            .filter(hummock_time_travel_delta::Column::VersionId.gt(checkpoint_version_id))
+            .count(txn)
+            .await?;
+        if pending_deltas < time_travel_checkpoint_interval() {
+            return Ok(());
+        }
+
+        let deltas = hummock_time_travel_delta::Entity::find()
+            .filter(hummock_time_travel_delta::Column::VersionId.gt(checkpoint_version_id))
+            .filter(
+                hummock_time_travel_delta::Column::VersionId.lte(
+                    risingwave_meta_model_v2::HummockVersionId::try_from(up_to_version_id)
+                        .unwrap(),
+                ),
+            )
+            .order_by_asc(hummock_time_travel_delta::Column::VersionId)
+            .all(txn)
+            .await?;
+        let checkpoint_version = replay_archive(
+            last_checkpoint.version.to_protobuf(),
+            deltas.into_iter().map(|d| d.version_delta.to_protobuf()),
+        );
+
+        let m = hummock_time_travel_version::ActiveModel {
+            version_id: Set(
+                risingwave_meta_model_v2::HummockVersionId::try_from(checkpoint_version.id)
+                    .unwrap(),
+            ),
+            version: Set((&IncompleteHummockVersion::from(&checkpoint_version).to_protobuf()).into()),
+        };
+        hummock_time_travel_version::Entity::insert(m)
+            .on_conflict(
+                OnConflict::column(hummock_time_travel_version::Column::VersionId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(txn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Default number of deltas written since the last full `hummock_time_travel_version` row before
+/// [`HummockManager::maybe_checkpoint_time_travel_version`] forces a new one. Bounds how many
+/// deltas `replay_archive` ever has to fold over to answer a single `epoch_to_version` query, at
+/// the cost of a little extra write amplification and storage. Overridable via
+/// `RW_TIME_TRAVEL_CHECKPOINT_INTERVAL`.
+const DEFAULT_TIME_TRAVEL_CHECKPOINT_INTERVAL: u64 = 100;
+
+fn time_travel_checkpoint_interval() -> u64 {
+    std::env::var("RW_TIME_TRAVEL_CHECKPOINT_INTERVAL")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TIME_TRAVEL_CHECKPOINT_INTERVAL)
 }
 
 fn replay_archive(
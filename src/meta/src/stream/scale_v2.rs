@@ -12,39 +12,177 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// pub struct Reschedule {}
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use mv2::FragmentId;
+use risingwave_meta_model_v2 as mv2;
+use risingwave_meta_model_v2::WorkerId;
+
+use crate::manager::MetadataManager;
+use crate::{MetaError, MetaResult};
+
+/// Per-worker quota enforced before a [`RescheduleV2`] plan is applied.
+///
+/// Mirrors a counter+quota admission check: [`WorkerActorQuota::max_actor_count`] bounds how many
+/// actors may ever be assigned to one worker at a time, independent of which fragment they belong
+/// to. `None` means "unbounded", matching today's behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorkerActorQuota {
+    pub max_actor_count: Option<usize>,
+}
 
 pub struct ScaleControllerV2 {
     metadata_manager: MetadataManager,
+    /// Per-worker quota, keyed by worker id. Workers absent from this map are unbounded.
+    quotas: HashMap<WorkerId, WorkerActorQuota>,
 }
 
 pub struct RescheduleV2 {
     plan: HashMap<FragmentId, HashMap<WorkerId, usize>>,
 }
 
-use std::collections::HashMap;
+impl ScaleControllerV2 {
+    pub fn new(metadata_manager: MetadataManager, quotas: HashMap<WorkerId, WorkerActorQuota>) -> Self {
+        Self {
+            metadata_manager,
+            quotas,
+        }
+    }
 
-use mv2::FragmentId;
-use risingwave_meta_model_v2 as mv2;
-use risingwave_meta_model_v2::WorkerId;
+    /// Applies `reschedule`, after checking that no worker's post-move actor count would exceed
+    /// its configured quota.
+    ///
+    /// The whole plan is rejected (no partial application) if any single worker would be pushed
+    /// over its limit, since a partially-applied reschedule would leave fragments in an
+    /// inconsistent parallelism.
+    pub async fn reschedule(&self, reschedule: RescheduleV2) -> MetaResult<()> {
+        let fragment_ids = reschedule.plan.keys().cloned().collect::<Vec<_>>();
 
-use crate::manager::MetadataManager;
-use crate::MetaResult;
+        // A worker's post-move total is everything it currently holds, minus what it currently
+        // holds in exactly the fragments this plan touches (which the plan's targets replace),
+        // plus those plan targets. Actors from fragments the plan doesn't touch must still count
+        // against quota, or a worker already near capacity from untouched fragments would be
+        // waved through.
+        let total_current = self.metadata_manager.count_all_actors_per_worker().await?;
+        let current_in_plan_fragments = self
+            .metadata_manager
+            .count_actors_per_worker(&fragment_ids)
+            .await?;
+        let projected_in_plan_fragments = compute_projected_actor_counts(&reschedule.plan);
+        let projected_total = project_actor_counts(
+            &total_current,
+            &current_in_plan_fragments,
+            &projected_in_plan_fragments,
+        );
 
-impl ScaleControllerV2 {
-    pub async fn reschedule(&self, _reschedule: RescheduleV2) -> MetaResult<()> {
-        // let metadata_manager = self.metadata_manager.as_v2_ref();
-        //
-        // let inner = metadata_manager.catalog_controller.inner.write().await;
-        // let txn = inner.db.begin().await?;
-        //
-        // let fragment_ids = reschedule.plan.keys().cloned().collect::<Vec<_>>();
-        //
-        // let _working_set = metadata_manager
-        //     .catalog_controller
-        //     .resolve_working_set_for_reschedule(&txn, fragment_ids)
-        //     .await?;
+        self.check_quota(&projected_total)?;
+
+        self.metadata_manager
+            .apply_reschedule_plan(reschedule.plan)
+            .await?;
 
         Ok(())
     }
+
+    /// Rejects the plan if any quota-bound worker's `projected_actor_counts` (its total post-move
+    /// actor count across every fragment it hosts, not just the ones `reschedule` touches) would
+    /// exceed [`WorkerActorQuota::max_actor_count`].
+    fn check_quota(&self, projected_actor_counts: &HashMap<WorkerId, usize>) -> MetaResult<()> {
+        for (worker_id, quota) in &self.quotas {
+            let Some(max_actor_count) = quota.max_actor_count else {
+                continue;
+            };
+            let projected = *projected_actor_counts.get(worker_id).unwrap_or(&0);
+            if projected > max_actor_count {
+                return Err(MetaError::from(anyhow!(
+                    "worker {} would hold {} actors after reschedule, exceeding its quota of {}",
+                    worker_id,
+                    projected,
+                    max_actor_count,
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Offline repair: recomputes the durable actor counter for every worker from the
+    /// authoritative fragment -> worker assignments, in case the counter has drifted (e.g. due to
+    /// a crash between applying a reschedule and persisting its counter update).
+    pub async fn repair_actor_counters(&self) -> MetaResult<HashMap<WorkerId, usize>> {
+        let authoritative_counts = self.metadata_manager.count_all_actors_per_worker().await?;
+        self.metadata_manager
+            .overwrite_actor_counters(authoritative_counts.clone())
+            .await?;
+        Ok(authoritative_counts)
+    }
+}
+
+/// Computes, for each worker touched by `plan`, its post-move actor count across just the
+/// fragments `plan` reassigns it: the sum of `plan`'s per-fragment target counts for that worker.
+///
+/// This is only the in-scope slice of a worker's total actor count -- `plan` specifies each
+/// touched fragment's full post-move actor placement, but says nothing about fragments it doesn't
+/// mention, so this alone cannot tell a worker's complete projected count; see
+/// [`project_actor_counts`] for that.
+fn compute_projected_actor_counts(
+    plan: &HashMap<FragmentId, HashMap<WorkerId, usize>>,
+) -> HashMap<WorkerId, usize> {
+    let mut projected: HashMap<WorkerId, usize> = HashMap::new();
+    for worker_targets in plan.values() {
+        for (worker_id, target_count) in worker_targets {
+            *projected.entry(*worker_id).or_default() += *target_count;
+        }
+    }
+    projected
+}
+
+/// Combines a worker's overall current actor count with what the plan changes: its current count
+/// within just the plan's fragments is replaced by the plan's post-move count for those same
+/// fragments, while actors from every other fragment the worker hosts carry over unchanged.
+fn project_actor_counts(
+    total_current: &HashMap<WorkerId, usize>,
+    current_in_plan_fragments: &HashMap<WorkerId, usize>,
+    projected_in_plan_fragments: &HashMap<WorkerId, usize>,
+) -> HashMap<WorkerId, usize> {
+    let mut projected = total_current.clone();
+    for (worker_id, in_scope_target) in projected_in_plan_fragments {
+        let in_scope_current = *current_in_plan_fragments.get(worker_id).unwrap_or(&0);
+        let entry = projected.entry(*worker_id).or_default();
+        *entry = entry.saturating_sub(in_scope_current) + in_scope_target;
+    }
+    projected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_projected_actor_counts() {
+        let mut plan = HashMap::new();
+        plan.insert(1, HashMap::from([(10, 2usize), (11, 1usize)]));
+        plan.insert(2, HashMap::from([(10, 3usize)]));
+
+        let projected = compute_projected_actor_counts(&plan);
+        assert_eq!(projected[&10], 5);
+        assert_eq!(projected[&11], 1);
+    }
+
+    #[test]
+    fn test_project_actor_counts_folds_in_untouched_fragments() {
+        // Worker 10 hosts 8 actors in total, 3 of which are in the fragment the plan touches; the
+        // plan raises that fragment's count on worker 10 to 5. The other 5 actors, from fragments
+        // the plan never mentions, must still be counted.
+        let total_current = HashMap::from([(10, 8usize)]);
+        let current_in_plan_fragments = HashMap::from([(10, 3usize)]);
+        let projected_in_plan_fragments = HashMap::from([(10, 5usize)]);
+
+        let projected = project_actor_counts(
+            &total_current,
+            &current_in_plan_fragments,
+            &projected_in_plan_fragments,
+        );
+        assert_eq!(projected[&10], 10);
+    }
 }
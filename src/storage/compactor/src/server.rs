@@ -14,9 +14,9 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::net::SocketAddr;
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use parking_lot::RwLock;
 use risingwave_common::config::{
@@ -50,13 +50,14 @@ use risingwave_storage::hummock::{
     SstableStore,
 };
 use risingwave_storage::monitor::{
-    monitor_cache, GLOBAL_COMPACTOR_METRICS, GLOBAL_HUMMOCK_METRICS,
+    monitor_cache, CompactorMetrics, GLOBAL_COMPACTOR_METRICS, GLOBAL_HUMMOCK_METRICS,
 };
 use risingwave_storage::opts::StorageOpts;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
-use tonic::transport::Channel;
+use tonic::transport::{Channel, Endpoint};
+use tower::discover::Change;
 use tracing::info;
 
 use super::compactor_observer::observer_manager::CompactorObserverNode;
@@ -64,6 +65,333 @@ use crate::rpc::{CompactorServiceImpl, MonitorServiceImpl};
 use crate::telemetry::CompactorTelemetryCreator;
 use crate::CompactorOpts;
 
+/// Refill tick for [`CompactionIoRateLimiter`]: the bucket gains `rate / REFILLS_PER_SEC` tokens
+/// this often, so a rate change made via a system-param update is felt within one tick.
+const IO_RATE_LIMITER_REFILL_INTERVAL: Duration = Duration::from_millis(100);
+const IO_RATE_LIMITER_REFILLS_PER_SEC: u64 =
+    1000 / IO_RATE_LIMITER_REFILL_INTERVAL.as_millis() as u64;
+
+/// Fallback for `compactor_upload_ram_buffer_mb` when the operator leaves it unset.
+const DEFAULT_UPLOAD_RAM_BUFFER_MB: u64 = 512;
+
+/// Fallback for `compactor_max_concurrent_dispatched_tasks` when the operator leaves it unset.
+const DEFAULT_MAX_CONCURRENT_DISPATCHED_TASKS: usize = 4;
+
+/// Token-bucket throttle shared by a compactor's SST reads and uploads, so one catch-up compactor
+/// can't saturate object-store bandwidth and starve foreground reads.
+///
+/// `rate` is the configured bytes/sec ceiling; it's an `AtomicU64` so [`CompactorObserverNode`]
+/// can update it in place when the `compactor_max_io_bytes_per_sec` system param changes, without
+/// restarting the node. A rate of `0` means unlimited: [`Self::acquire`] returns immediately
+/// without touching the bucket.
+pub struct CompactionIoRateLimiter {
+    rate: AtomicU64,
+    available_tokens: AtomicU64,
+}
+
+impl CompactionIoRateLimiter {
+    pub fn new(rate: u64) -> Arc<Self> {
+        Arc::new(Self {
+            rate: AtomicU64::new(rate),
+            available_tokens: AtomicU64::new(rate),
+        })
+    }
+
+    /// Updates the bandwidth ceiling; takes effect on the next refill tick. `0` disables
+    /// throttling.
+    pub fn set_rate(&self, rate: u64) {
+        self.rate.store(rate, Ordering::Relaxed);
+    }
+
+    /// Blocks until `len` bytes' worth of tokens are available and deducts them, or returns
+    /// immediately if the limiter is currently unlimited (`rate == 0`).
+    ///
+    /// A single refill window never holds more than `rate` tokens (the refill task caps
+    /// `available_tokens` at `rate`), so a `len` bigger than that -- an SST block larger than one
+    /// second's configured budget is a common case -- would wait forever for `available >= len`
+    /// to hold in one window. Drain it across as many ticks as it takes instead, `rate` tokens at
+    /// a time.
+    pub async fn acquire(&self, len: u64) {
+        let mut remaining = len;
+        loop {
+            let rate = self.rate.load(Ordering::Relaxed);
+            if rate == 0 {
+                return;
+            }
+
+            let take = remaining.min(rate);
+            let available = self.available_tokens.load(Ordering::Relaxed);
+            if available >= take {
+                let taken = self.available_tokens.fetch_sub(take, Ordering::Relaxed);
+                if taken >= take {
+                    remaining -= take;
+                    if remaining == 0 {
+                        return;
+                    }
+                } else {
+                    // Lost the race to another acquirer; undo and retry after the next refill.
+                    self.available_tokens.fetch_add(take, Ordering::Relaxed);
+                }
+            }
+
+            tokio::time::sleep(IO_RATE_LIMITER_REFILL_INTERVAL).await;
+        }
+    }
+
+    /// Spawns the background task that refills the bucket every
+    /// [`IO_RATE_LIMITER_REFILL_INTERVAL`], capped at one second's worth of the current rate.
+    pub fn start_refill_task(self: &Arc<Self>) -> JoinHandle<()> {
+        let limiter = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(IO_RATE_LIMITER_REFILL_INTERVAL);
+            loop {
+                interval.tick().await;
+                let rate = limiter.rate.load(Ordering::Relaxed);
+                if rate == 0 {
+                    limiter.available_tokens.store(0, Ordering::Relaxed);
+                    continue;
+                }
+                let refill = rate / IO_RATE_LIMITER_REFILLS_PER_SEC;
+                limiter
+                    .available_tokens
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |available| {
+                        Some((available + refill).min(rate))
+                    })
+                    .ok();
+            }
+        })
+    }
+}
+
+/// Caps the total size of finished SST blocks sitting in RAM while they wait for their
+/// asynchronous upload to the object store to complete.
+///
+/// Unlike [`MemoryLimiter`], which bounds in-use builder memory, this bounds the queue of
+/// already-built blocks a slow object store backend could otherwise let grow unbounded. Permits
+/// represent bytes: a task acquires `len` permits before handing a block off for upload and the
+/// returned [`UploadRamPermit`] releases them (and updates the buffered-bytes gauge) once the
+/// upload completes and the guard is dropped. When the buffer is full, `acquire` awaits, so
+/// compaction naturally slows to match upload throughput instead of buffering unbounded data.
+pub struct UploadRamBuffer {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    buffered_bytes: Arc<AtomicU64>,
+    compactor_metrics: Arc<CompactorMetrics>,
+    max_bytes: u64,
+}
+
+impl UploadRamBuffer {
+    pub fn new(max_bytes: u64, compactor_metrics: Arc<CompactorMetrics>) -> Arc<Self> {
+        Arc::new(Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(max_bytes as usize)),
+            buffered_bytes: Arc::new(AtomicU64::new(0)),
+            compactor_metrics,
+            max_bytes,
+        })
+    }
+
+    /// Awaits until `len` bytes of buffer space are free, then reserves them until the returned
+    /// guard is dropped.
+    ///
+    /// A block bigger than the whole buffer is clamped to `max_bytes`: it reserves the entire
+    /// buffer rather than waiting forever for more permits than the semaphore will ever hold. The
+    /// clamped amount is still acquired as a `u64` byte count; since
+    /// `Semaphore::acquire_many_owned` only takes a `u32`, it's requested in `u32::MAX`-sized
+    /// chunks so a buffer configured at or beyond 4 GiB doesn't get silently truncated.
+    pub async fn acquire(self: &Arc<Self>, len: u64) -> UploadRamPermit {
+        let reserved = len.min(self.max_bytes);
+
+        let mut permits = Vec::new();
+        let mut remaining = reserved;
+        while remaining > 0 {
+            let chunk = remaining.min(u32::MAX as u64) as u32;
+            let permit = self
+                .semaphore
+                .clone()
+                .acquire_many_owned(chunk)
+                .await
+                .expect("upload ram buffer semaphore should never be closed");
+            permits.push(permit);
+            remaining -= chunk as u64;
+        }
+
+        let buffered = self.buffered_bytes.fetch_add(reserved, Ordering::Relaxed) + reserved;
+        self.compactor_metrics
+            .compactor_upload_ram_buffer_bytes
+            .set(buffered as i64);
+
+        UploadRamPermit {
+            _permits: permits,
+            len: reserved,
+            buffered_bytes: self.buffered_bytes.clone(),
+            compactor_metrics: self.compactor_metrics.clone(),
+        }
+    }
+}
+
+/// Releases its share of [`UploadRamBuffer`] on drop, once the upload it was acquired for
+/// completes.
+pub struct UploadRamPermit {
+    _permits: Vec<tokio::sync::OwnedSemaphorePermit>,
+    len: u64,
+    buffered_bytes: Arc<AtomicU64>,
+    compactor_metrics: Arc<CompactorMetrics>,
+}
+
+impl Drop for UploadRamPermit {
+    fn drop(&mut self) {
+        let buffered = self.buffered_bytes.fetch_sub(self.len, Ordering::Relaxed) - self.len;
+        self.compactor_metrics
+            .compactor_upload_ram_buffer_bytes
+            .set(buffered as i64);
+    }
+}
+
+/// Starting backoff for [`spawn_meta_connection_supervisor`]'s reconnect loop; doubles on every
+/// failed attempt up to [`META_RECONNECT_BACKOFF_MAX`].
+const META_RECONNECT_BACKOFF_INIT: Duration = Duration::from_millis(200);
+const META_RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Minimum gap between two "meta unreachable" log emissions for the same endpoint, so a string of
+/// failed retries during one partition logs once per window instead of once per attempt.
+const META_UNREACHABLE_REPORT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Tracks whether the compactor's meta connection looks healthy, so the reconnect loop can
+/// suppress duplicate "unreachable" emissions during a single outage.
+///
+/// `successful_rpc_count` increases on every successful registration; heartbeat successes are not
+/// counted. `last_report` is the instant an "unreachable" event was last emitted, starting at
+/// construction. Both callers pass the count they read right before calling
+/// [`Self::should_report_unreachable`], so its count check never suppresses an event: a fresh
+/// event is emitted whenever the window has elapsed since `last_report`, whether or not an RPC
+/// succeeded in between. A string of retries during one partition thus logs once per window
+/// instead of once per attempt.
+struct MetaReachability {
+    last_report: parking_lot::Mutex<Instant>,
+    successful_rpc_count: AtomicU64,
+}
+
+impl MetaReachability {
+    fn new() -> Self {
+        Self {
+            last_report: parking_lot::Mutex::new(Instant::now()),
+            successful_rpc_count: AtomicU64::new(0),
+        }
+    }
+
+    fn record_success(&self) {
+        self.successful_rpc_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns `true` (and records the report) if an "unreachable" event should be emitted now:
+    /// the backoff window has passed since the last report and `successful_rpc_count` hasn't
+    /// moved past `last_seen_success_count` in the meantime.
+    fn should_report_unreachable(&self, last_seen_success_count: u64) -> bool {
+        if self.successful_rpc_count.load(Ordering::Relaxed) != last_seen_success_count {
+            return false;
+        }
+
+        let mut last_report = self.last_report.lock();
+        if last_report.elapsed() < META_UNREACHABLE_REPORT_WINDOW {
+            return false;
+        }
+        *last_report = Instant::now();
+        true
+    }
+}
+
+/// Registers with meta, retrying with exponential backoff (starting at
+/// [`META_RECONNECT_BACKOFF_INIT`], doubling up to [`META_RECONNECT_BACKOFF_MAX`]) until it
+/// succeeds, reporting a suppressed "unreachable" event through `reachability` on the way.
+async fn register_with_backoff(
+    meta_address: &str,
+    advertise_addr: &HostAddr,
+    config: &RwConfig,
+    reachability: &MetaReachability,
+) -> MetaClient {
+    let mut backoff = META_RECONNECT_BACKOFF_INIT;
+    loop {
+        match MetaClient::register_new(
+            meta_address,
+            WorkerType::Compactor,
+            advertise_addr,
+            Default::default(),
+            &config.meta,
+        )
+        .await
+        {
+            Ok((meta_client, _system_params)) => {
+                reachability.record_success();
+                return meta_client;
+            }
+            Err(e) => {
+                let last_seen = reachability.successful_rpc_count.load(Ordering::Relaxed);
+                if reachability.should_report_unreachable(last_seen) {
+                    tracing::warn!(
+                        "meta endpoint {} unreachable, retrying: {}",
+                        meta_address,
+                        e.as_report()
+                    );
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(META_RECONNECT_BACKOFF_MAX);
+            }
+        }
+    }
+}
+
+/// Supervises the compactor's connection to meta: runs the heartbeat loop against `meta_client`,
+/// and on its failure re-registers with [`register_with_backoff`], reactivates the worker, and
+/// resumes heartbeats, instead of leaving the compactor firing heartbeats at a dead connection.
+fn spawn_meta_connection_supervisor(
+    meta_address: String,
+    advertise_addr: HostAddr,
+    config: RwConfig,
+    heartbeat_interval: Duration,
+    heartbeat_infos: Vec<Arc<SstableObjectIdManager>>,
+    mut meta_client: MetaClient,
+) -> (JoinHandle<()>, Sender<()>) {
+    let reachability = Arc::new(MetaReachability::new());
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
+
+    let join_handle = tokio::spawn(async move {
+        loop {
+            let (heartbeat_handle, heartbeat_shutdown) = MetaClient::start_heartbeat_loop(
+                meta_client.clone(),
+                heartbeat_interval,
+                heartbeat_infos.clone(),
+            );
+
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    let _ = heartbeat_shutdown.send(());
+                    let _ = heartbeat_handle.await;
+                    return;
+                }
+                _ = heartbeat_handle => {}
+            }
+
+            let last_seen = reachability.successful_rpc_count.load(Ordering::Relaxed);
+            if reachability.should_report_unreachable(last_seen) {
+                tracing::warn!(
+                    "lost heartbeat connection to meta at {}, reconnecting",
+                    meta_address
+                );
+            }
+
+            let new_meta_client =
+                register_with_backoff(&meta_address, &advertise_addr, &config, &reachability).await;
+            if let Err(e) = new_meta_client.activate(&advertise_addr).await {
+                tracing::warn!(
+                    "failed to reactivate worker after meta reconnect: {}",
+                    e.as_report()
+                );
+            }
+            meta_client = new_meta_client;
+        }
+    });
+
+    (join_handle, shutdown_tx)
+}
+
 pub async fn prepare_start_parameters(
     config: RwConfig,
 
@@ -204,6 +532,17 @@ pub async fn compactor_serve(
     .await;
 
     let telemetry_enabled = system_params_reader.telemetry_enabled();
+    let io_rate_limiter =
+        CompactionIoRateLimiter::new(system_params_reader.compactor_max_io_bytes_per_sec());
+    io_rate_limiter.start_refill_task();
+
+    let upload_ram_buffer_max_bytes = config
+        .storage
+        .compactor_upload_ram_buffer_mb
+        .unwrap_or(DEFAULT_UPLOAD_RAM_BUFFER_MB)
+        * (1 << 20);
+    let upload_ram_buffer =
+        UploadRamBuffer::new(upload_ram_buffer_max_bytes, compactor_metrics.clone());
 
     let filter_key_extractor_manager = Arc::new(RpcFilterKeyExtractorManager::new(Box::new(
         RemoteTableAccessor::new(meta_client.clone()),
@@ -212,6 +551,7 @@ pub async fn compactor_serve(
     let compactor_observer_node = CompactorObserverNode::new(
         filter_key_extractor_manager.clone(),
         system_params_manager.clone(),
+        io_rate_limiter.clone(),
     );
     let observer_manager =
         ObserverManager::new_with_meta_client(meta_client.clone(), compactor_observer_node).await;
@@ -236,16 +576,21 @@ pub async fn compactor_serve(
             opts.compaction_worker_threads_number,
         )),
         memory_limiter,
+        io_rate_limiter: io_rate_limiter.clone(),
+        upload_ram_buffer: upload_ram_buffer.clone(),
 
         task_progress_manager: Default::default(),
         await_tree_reg: await_tree_reg.clone(),
         running_task_count: Arc::new(AtomicU32::new(0)),
     };
     let mut sub_tasks = vec![
-        MetaClient::start_heartbeat_loop(
-            meta_client.clone(),
+        spawn_meta_connection_supervisor(
+            opts.meta_address.clone(),
+            advertise_addr.clone(),
+            config.clone(),
             Duration::from_millis(config.server.heartbeat_interval_ms as u64),
             vec![sstable_object_id_manager.clone()],
+            meta_client.clone(),
         ),
         risingwave_storage::hummock::compactor::start_compactor(
             compactor_context.clone(),
@@ -313,12 +658,91 @@ pub async fn compactor_serve(
     (join_handle, observer_join_handle, shutdown_send)
 }
 
+/// How often [`connect_with_failover`] re-resolves `opts.proxy_rpc_endpoint` and pushes any
+/// membership changes into the load-balanced channel.
+const PROXY_ENDPOINT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Builds a self-healing channel to the serverless compactor's proxy/meta `HummockManager`
+/// endpoint(s).
+///
+/// `endpoints` is the raw `--proxy-rpc-endpoint` value: either a single URI (the degenerate,
+/// pre-existing case), a comma-separated list of URIs, or a bare DNS name that resolves to
+/// several A records. All three shapes are normalized into a set of resolved URIs that's
+/// periodically refreshed and fed into a [`tonic`] load-balanced channel, so the client
+/// transparently fails over to another endpoint and reconnects when one goes away instead of
+/// requiring a compactor restart, matching how cluster-discovery-enabled nodes ride out control
+/// plane rescheduling.
+async fn connect_with_failover(endpoints: String) -> Channel {
+    let (channel, sender) = Channel::balance_channel(16);
+    let mut known = HashMap::new();
+    refresh_proxy_endpoints(&endpoints, &mut known, &sender).await;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PROXY_ENDPOINT_REFRESH_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            refresh_proxy_endpoints(&endpoints, &mut known, &sender).await;
+        }
+    });
+
+    channel
+}
+
+/// Resolves `endpoints` (comma-separated URIs and/or DNS names) into a key -> [`Endpoint`] set
+/// and diffs it against `known`, pushing [`Change::Insert`]/[`Change::Remove`] for anything that
+/// changed since the last refresh.
+async fn refresh_proxy_endpoints(
+    endpoints: &str,
+    known: &mut HashMap<String, ()>,
+    sender: &mpsc::Sender<Change<String, Endpoint>>,
+) {
+    let mut resolved = HashMap::new();
+
+    for raw in endpoints
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        if raw.contains("://") {
+            resolved.insert(raw.to_string(), ());
+            continue;
+        }
+
+        match tokio::net::lookup_host(raw).await {
+            Ok(addrs) => {
+                for addr in addrs {
+                    resolved.insert(format!("http://{}", addr), ());
+                }
+            }
+            Err(e) => {
+                tracing::warn!("failed to resolve compactor proxy endpoint {}: {}", raw, e);
+            }
+        }
+    }
+
+    for key in resolved.keys() {
+        if known.contains_key(key) {
+            continue;
+        }
+        if let Ok(endpoint) = Endpoint::from_shared(key.clone()) {
+            let _ = sender.send(Change::Insert(key.clone(), endpoint)).await;
+        }
+    }
+    for key in known.keys() {
+        if !resolved.contains_key(key) {
+            let _ = sender.send(Change::Remove(key.clone())).await;
+        }
+    }
+
+    *known = resolved;
+}
+
 pub async fn shared_compactor_serve(
     listen_addr: SocketAddr,
     opts: CompactorOpts,
 ) -> (JoinHandle<()>, Sender<()>) {
-    let endpoint: &'static str = Box::leak(opts.proxy_rpc_endpoint.clone().into_boxed_str());
-    let channel = Channel::from_static(endpoint).connect().await.unwrap();
+    let channel = connect_with_failover(opts.proxy_rpc_endpoint.clone()).await;
 
     let client: HummockManagerServiceClient<Channel> = HummockManagerServiceClient::new(channel);
 
@@ -363,13 +787,40 @@ pub async fn shared_compactor_serve(
         &opts.state_store_url,
     )
     .await;
+    // No system-param watcher in the serverless path, so the rate starts unlimited; wiring a live
+    // update here would go through the same `CompactorObserverNode` as `compactor_serve`.
+    let io_rate_limiter = CompactionIoRateLimiter::new(0);
+    io_rate_limiter.start_refill_task();
+    let upload_ram_buffer_max_bytes = config
+        .storage
+        .compactor_upload_ram_buffer_mb
+        .unwrap_or(DEFAULT_UPLOAD_RAM_BUFFER_MB)
+        * (1 << 20);
+    let upload_ram_buffer =
+        UploadRamBuffer::new(upload_ram_buffer_max_bytes, compactor_metrics.clone());
+    let max_concurrent_dispatched_tasks = config
+        .storage
+        .compactor_max_concurrent_dispatched_tasks
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_DISPATCHED_TASKS);
+    let dispatch_semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrent_dispatched_tasks));
     let (sender, mut receiver) = mpsc::unbounded_channel();
     let compactor_srv: CompactorServiceImpl = CompactorServiceImpl::new(sender);
 
     let cloned_await_tree_reg = await_tree_reg.clone();
 
     let monitor_srv = MonitorServiceImpl::new(await_tree_reg);
-    let (shutdown_send, mut shutdown_recv) = tokio::sync::oneshot::channel();
+    let (shutdown_send, shutdown_recv) = tokio::sync::oneshot::channel();
+    // Bridges the externally-facing oneshot shutdown signal into a `watch` so every
+    // concurrently-dispatched compaction task (and the receive loop) can observe it, instead of
+    // the single-use oneshot only being awaitable once.
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {},
+            _ = shutdown_recv => {},
+        }
+        let _ = stop_tx.send(true);
+    });
     let join_handle = tokio::spawn(async move {
         tonic::transport::Server::builder()
             .add_service(CompactorServiceServer::new(compactor_srv))
@@ -383,63 +834,85 @@ pub async fn shared_compactor_serve(
                 },
                 async move {
                     loop {
+                        let mut loop_stop_rx = stop_rx.clone();
                         tokio::select! {
+                            _ = loop_stop_rx.changed() => {
+                                break;
+                            }
                             request = receiver.recv() => {
-                                if let Some(request) = request {
-                                    let DispatchCompactionTaskRequest {
-                                        tables,
-                                        output_object_ids,
-                                        task: dispatch_task,
-                                    } = request.into_inner();
-                                    let id_to_tables = tables.into_iter().fold(HashMap::new(), |mut acc, table| {
-                                        acc.insert(table.id, table);
-                                        acc
-                                    });
-                                    let static_filter_key_extractor_manager: Arc<StaticFilterKeyExtractorManager> =
-                                        Arc::new(StaticFilterKeyExtractorManager::new(id_to_tables));
-                                    let filter_key_extractor_manager =
-                                        FilterKeyExtractorManager::StaticFilterKeyExtractorManager(
-                                            static_filter_key_extractor_manager,
-                                        );
-                                    let compactor_context = CompactorContext {
-                                        storage_opts: storage_opts.clone(),
-                                        sstable_store: sstable_store.clone(),
-                                        compactor_metrics: compactor_metrics.clone(),
-                                        is_share_buffer_compact: false,
-                                        compaction_executor: Arc::new(CompactionExecutor::new(
-                                            opts.compaction_worker_threads_number,
-                                        )),
-                                        memory_limiter: memory_limiter.clone(),
-                                        task_progress_manager: Default::default(),
-                                        await_tree_reg: cloned_await_tree_reg.clone(),
-                                        running_task_count: Arc::new(AtomicU32::new(0)),
-                                    };
-                                    let mut output_object_ids_deque: VecDeque<_> = VecDeque::new();
-                                    output_object_ids_deque.extend(output_object_ids);
-                                    let shared_compactor_object_id_manager =
-                                        SharedComapctorObjectIdManager::new(output_object_ids_deque);
-                                    let (join_handle, shutdown_sender) =
+                                let Some(request) = request else {
+                                    continue;
+                                };
+                                let DispatchCompactionTaskRequest {
+                                    tables,
+                                    output_object_ids,
+                                    task: dispatch_task,
+                                } = request.into_inner();
+                                let id_to_tables = tables.into_iter().fold(HashMap::new(), |mut acc, table| {
+                                    acc.insert(table.id, table);
+                                    acc
+                                });
+                                let static_filter_key_extractor_manager: Arc<StaticFilterKeyExtractorManager> =
+                                    Arc::new(StaticFilterKeyExtractorManager::new(id_to_tables));
+                                let filter_key_extractor_manager =
+                                    FilterKeyExtractorManager::StaticFilterKeyExtractorManager(
+                                        static_filter_key_extractor_manager,
+                                    );
+                                let compactor_context = CompactorContext {
+                                    storage_opts: storage_opts.clone(),
+                                    sstable_store: sstable_store.clone(),
+                                    compactor_metrics: compactor_metrics.clone(),
+                                    is_share_buffer_compact: false,
+                                    compaction_executor: Arc::new(CompactionExecutor::new(
+                                        opts.compaction_worker_threads_number,
+                                    )),
+                                    memory_limiter: memory_limiter.clone(),
+                                    io_rate_limiter: io_rate_limiter.clone(),
+                                    upload_ram_buffer: upload_ram_buffer.clone(),
+                                    task_progress_manager: Default::default(),
+                                    await_tree_reg: cloned_await_tree_reg.clone(),
+                                    running_task_count: Arc::new(AtomicU32::new(0)),
+                                };
+                                let mut output_object_ids_deque: VecDeque<_> = VecDeque::new();
+                                output_object_ids_deque.extend(output_object_ids);
+                                let shared_compactor_object_id_manager =
+                                    SharedComapctorObjectIdManager::new(output_object_ids_deque);
+
+                                // Bound how many dispatched tasks run at once: at most
+                                // `max_concurrent_dispatched_tasks` run concurrently, one
+                                // `tokio::spawn` per dispatched task. While every permit is held
+                                // the `recv` loop waits here, so later requests stay queued in the
+                                // channel until a running task finishes.
+                                let permit = dispatch_semaphore.clone().acquire_owned().await.unwrap();
+                                let client = client.clone();
+                                let filter_key_extractor_manager = filter_key_extractor_manager.clone();
+                                let mut task_stop_rx = stop_rx.clone();
+                                tokio::spawn(async move {
+                                    let (mut join_handle, shutdown_sender) =
                                         risingwave_storage::hummock::compactor::start_shared_compactor(
-                                            client.clone(),
+                                            client,
                                             dispatch_task.unwrap(),
                                             compactor_context,
                                             Box::new(shared_compactor_object_id_manager),
-                                            filter_key_extractor_manager.clone()
+                                            filter_key_extractor_manager,
                                         );
                                     tokio::select! {
-                                        _ = tokio::signal::ctrl_c() => {},
-                                        _ = &mut shutdown_recv => {
-                                                if let Err(err) = shutdown_sender.send(()) {
-                                                    tracing::warn!("Failed to send shutdown: {:?}", err);
-                                                }
-                                                if let Err(err) = join_handle.await {
-                                                    tracing::warn!("Failed to join shutdown: {:?}", err);
-                                                }
-                                        },
+                                        result = &mut join_handle => {
+                                            if let Err(err) = result {
+                                                tracing::warn!("Failed to join compaction task: {:?}", err);
+                                            }
+                                        }
+                                        _ = task_stop_rx.changed() => {
+                                            if let Err(err) = shutdown_sender.send(()) {
+                                                tracing::warn!("Failed to send shutdown: {:?}", err);
+                                            }
+                                            if let Err(err) = (&mut join_handle).await {
+                                                tracing::warn!("Failed to join shutdown: {:?}", err);
+                                            }
+                                        }
                                     }
-                                } else {
-                                    continue;
-                                }
+                                    drop(permit);
+                                });
                             }
                         };
                     }
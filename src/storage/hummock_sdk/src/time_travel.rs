@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use prost::Message;
 use risingwave_common::catalog::TableId;
 use risingwave_pb::hummock::group_delta::DeltaType;
 use risingwave_pb::hummock::hummock_version::PbLevels;
@@ -43,6 +45,35 @@ pub struct IncompleteHummockVersion {
     pub state_table_info: HummockVersionStateTableInfo,
 }
 
+/// An error produced when reconstructing a version/delta: a missing entry in `sst_id_to_info`
+/// while refilling stripped `SstableInfo`s, or a compact delta frame that fails to decode.
+pub type RefillError = String;
+pub type RefillResult<T> = std::result::Result<T, RefillError>;
+
+/// A cheap (non-cryptographic) checksum over an `SstableInfo`'s non-`sst_id` fields, i.e. exactly
+/// the fields [`stripped_sstable_info`] discards. `hummock_sstable_info` rows are inserted
+/// first-write-wins per `sst_id` (see `write_sstable_infos` in
+/// `risingwave_meta::hummock::manager::time_travel`), so on a write that finds an existing row
+/// under the same `sst_id` this lets the caller detect whether that `sst_id` was recycled for a
+/// different SST -- which `do_nothing`-on-conflict would otherwise silently paper over -- instead
+/// of trusting the pre-existing row unconditionally.
+pub fn checksum_sstable_info(origin: &SstableInfo) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    origin.object_id.hash(&mut hasher);
+    origin.key_range.hash(&mut hasher);
+    origin.file_size.hash(&mut hasher);
+    origin.table_ids.hash(&mut hasher);
+    origin.meta_offset.hash(&mut hasher);
+    origin.stale_key_count.hash(&mut hasher);
+    origin.total_key_count.hash(&mut hasher);
+    origin.min_epoch.hash(&mut hasher);
+    origin.max_epoch.hash(&mut hasher);
+    origin.uncompressed_file_size.hash(&mut hasher);
+    origin.range_tombstone_count.hash(&mut hasher);
+    origin.bloom_filter_kind.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Clone from an `SstableInfo`, but only set the `sst_id` for the target, leaving other fields as default.
 fn stripped_sstable_info(origin: &SstableInfo) -> SstableInfo {
     SstableInfo {
@@ -96,7 +127,7 @@ fn stripped_level(origin: &PbLevel) -> PbLevel {
 pub fn refill_version(
     version: &mut HummockVersion,
     sst_id_to_info: &HashMap<HummockSstableId, PbSstableInfo>,
-) {
+) -> RefillResult<()> {
     for level in version.levels.values_mut().flat_map(|level| {
         level
             .l0
@@ -107,43 +138,54 @@ pub fn refill_version(
             .rev()
             .chain(level.levels.iter_mut())
     }) {
-        refill_level(level, sst_id_to_info);
+        refill_level(level, sst_id_to_info)?;
     }
 
     for t in version.table_change_log.values_mut() {
-        refill_table_change_log(t, sst_id_to_info);
+        refill_table_change_log(t, sst_id_to_info)?;
     }
+    Ok(())
 }
 
-fn refill_level(level: &mut PbLevel, sst_id_to_info: &HashMap<HummockSstableId, PbSstableInfo>) {
+fn refill_level(
+    level: &mut PbLevel,
+    sst_id_to_info: &HashMap<HummockSstableId, PbSstableInfo>,
+) -> RefillResult<()> {
     for s in &mut level.table_infos {
-        refill_sstable_info(s, sst_id_to_info);
+        refill_sstable_info(s, sst_id_to_info)?;
     }
+    Ok(())
 }
 
 fn refill_table_change_log(
     table_change_log: &mut TableChangeLog,
     sst_id_to_info: &HashMap<HummockSstableId, PbSstableInfo>,
-) {
+) -> RefillResult<()> {
     for c in &mut table_change_log.0 {
         for s in &mut c.old_value {
-            refill_sstable_info(s, sst_id_to_info);
+            refill_sstable_info(s, sst_id_to_info)?;
         }
         for s in &mut c.new_value {
-            refill_sstable_info(s, sst_id_to_info);
+            refill_sstable_info(s, sst_id_to_info)?;
         }
     }
+    Ok(())
 }
 
-/// Caller should ensure `sst_id_to_info` includes an entry corresponding to `sstable_info`.
+/// Replaces the stripped `sstable_info` with the full entry looked up by `sst_id`. The full entry
+/// itself is integrity-checked against [`checksum_sstable_info`] at write time (see
+/// `write_sstable_infos` in `risingwave_meta::hummock::manager::time_travel`), so by the time it's
+/// read back here it's already known to be the SST that was actually stripped.
 fn refill_sstable_info(
     sstable_info: &mut PbSstableInfo,
     sst_id_to_info: &HashMap<HummockSstableId, PbSstableInfo>,
-) {
-    *sstable_info = sst_id_to_info
-        .get(&sstable_info.sst_id)
-        .unwrap_or_else(|| panic!("SstableInfo should exist"))
-        .clone();
+) -> RefillResult<()> {
+    let sst_id = sstable_info.sst_id;
+    let full_info = sst_id_to_info
+        .get(&sst_id)
+        .ok_or_else(|| format!("SstableInfo for sst_id {} should exist", sst_id))?;
+    *sstable_info = full_info.clone();
+    Ok(())
 }
 
 fn stripped_l0(origin: &PbOverlappingLevel) -> PbOverlappingLevel {
@@ -202,28 +244,31 @@ fn stripped_group_deltas(origin: &PbGroupDeltas) -> PbGroupDeltas {
 /// `SStableInfo` will be stripped.
 impl From<&HummockVersion> for IncompleteHummockVersion {
     fn from(version: &HummockVersion) -> Self {
+        let levels = version
+            .levels
+            .iter()
+            .map(|(group_id, levels)| (*group_id as CompactionGroupId, stripped_levels(levels)))
+            .collect();
+        let table_change_log = version
+            .table_change_log
+            .iter()
+            .map(|(table_id, change_log)| {
+                let incomplete_table_change_log = change_log
+                    .0
+                    .iter()
+                    .map(stripped_epoch_new_change_log)
+                    .collect();
+                (*table_id, TableChangeLog(incomplete_table_change_log))
+            })
+            .collect();
+
         Self {
             id: version.id,
-            levels: version
-                .levels
-                .iter()
-                .map(|(group_id, levels)| (*group_id as CompactionGroupId, stripped_levels(levels)))
-                .collect(),
+            levels,
             max_committed_epoch: version.max_committed_epoch,
             safe_epoch: version.visible_table_safe_epoch(),
             table_watermarks: version.table_watermarks.clone(),
-            table_change_log: version
-                .table_change_log
-                .iter()
-                .map(|(table_id, change_log)| {
-                    let incomplete_table_change_log = change_log
-                        .0
-                        .iter()
-                        .map(stripped_epoch_new_change_log)
-                        .collect();
-                    (*table_id, TableChangeLog(incomplete_table_change_log))
-                })
-                .collect(),
+            table_change_log,
             state_table_info: version.state_table_info.clone(),
         }
     }
@@ -332,3 +377,299 @@ impl IncompleteHummockVersionDelta {
         }
     }
 }
+
+/// zstd level used for [`IncompleteHummockVersionDelta::to_compact_protobuf`]. Chosen for low CPU
+/// cost at the broadcast rate this runs at, not for maximum ratio.
+const COMPACT_DELTA_ZSTD_LEVEL: i32 = 3;
+
+/// A wire-compact encoding of an [`IncompleteHummockVersionDelta`], diffed against the
+/// predecessor the receiver is assumed to already hold. Meta ships a stream of these to
+/// followers/compute nodes, and consecutive deltas repeat large amounts of near-identical
+/// `group_deltas` / `state_table_info_delta` / `change_log_delta` structure, so only the entries
+/// that actually changed are carried; unchanged entries are referenced by key and resolved
+/// against the predecessor by [`IncompleteHummockVersionDelta::reconstruct`]. The resulting
+/// buffer is zstd-compressed on top.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactVersionDeltaFrame {
+    /// Must equal the `id` of the delta the receiver reconstructs against. If it doesn't, the
+    /// receiver's copy has diverged (e.g. a dropped message) and it must fall back to requesting
+    /// a full, uncompressed delta instead of calling
+    /// [`IncompleteHummockVersionDelta::reconstruct`].
+    pub prev_id: u64,
+    pub id: u64,
+    payload: Vec<u8>,
+}
+
+fn write_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(buf, bytes.len() as u32);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_message(buf: &mut Vec<u8>, message: &impl Message) {
+    write_bytes(buf, &message.encode_to_vec());
+}
+
+struct Reader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(rest: &'a [u8]) -> Self {
+        Self { rest }
+    }
+
+    fn read_u8(&mut self) -> RefillResult<u8> {
+        if self.rest.is_empty() {
+            return Err("truncated compact version delta payload".to_string());
+        }
+        let (head, tail) = self.rest.split_at(1);
+        self.rest = tail;
+        Ok(head[0])
+    }
+
+    fn read_u32(&mut self) -> RefillResult<u32> {
+        if self.rest.len() < 4 {
+            return Err("truncated compact version delta payload".to_string());
+        }
+        let (head, tail) = self.rest.split_at(4);
+        self.rest = tail;
+        Ok(u32::from_le_bytes(head.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> RefillResult<u64> {
+        if self.rest.len() < 8 {
+            return Err("truncated compact version delta payload".to_string());
+        }
+        let (head, tail) = self.rest.split_at(8);
+        self.rest = tail;
+        Ok(u64::from_le_bytes(head.try_into().unwrap()))
+    }
+
+    fn read_bytes(&mut self) -> RefillResult<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        if self.rest.len() < len {
+            return Err("truncated compact version delta payload".to_string());
+        }
+        let (head, tail) = self.rest.split_at(len);
+        self.rest = tail;
+        Ok(head)
+    }
+
+    fn read_message<T: Message + Default>(&mut self) -> RefillResult<T> {
+        let bytes = self.read_bytes()?;
+        T::decode(bytes).map_err(|e| format!("corrupt compact version delta payload: {e}"))
+    }
+}
+
+impl IncompleteHummockVersionDelta {
+    /// Builds a [`CompactVersionDeltaFrame`] for this delta relative to `prev`, the delta the
+    /// receiver is assumed to already hold in full (i.e. `prev.id == self.prev_id`).
+    ///
+    /// Only `group_deltas` / `state_table_info_delta` / `change_log_delta` entries that differ
+    /// from `prev`'s are encoded in full; entries that are identical are carried as a bare key,
+    /// to be resolved back against `prev` by [`Self::reconstruct`]. The remaining fields are
+    /// comparatively small and are always encoded in full.
+    pub fn to_compact_protobuf(&self, prev: &IncompleteHummockVersionDelta) -> CompactVersionDeltaFrame {
+        debug_assert_eq!(
+            prev.id, self.prev_id,
+            "compact version delta must be diffed against its immediate predecessor"
+        );
+
+        let mut changed_group_deltas = vec![];
+        let mut unchanged_group_ids = vec![];
+        for (cg_id, deltas) in &self.group_deltas {
+            if prev.group_deltas.get(cg_id) == Some(deltas) {
+                unchanged_group_ids.push(*cg_id);
+            } else {
+                changed_group_deltas.push((*cg_id, deltas));
+            }
+        }
+
+        let mut changed_state_table_info_delta = vec![];
+        let mut unchanged_state_table_info_delta_ids = vec![];
+        for (table_id, delta) in &self.state_table_info_delta {
+            if prev.state_table_info_delta.get(table_id) == Some(delta) {
+                unchanged_state_table_info_delta_ids.push(*table_id);
+            } else {
+                changed_state_table_info_delta.push((*table_id, delta));
+            }
+        }
+
+        let mut changed_change_log_delta = vec![];
+        let mut unchanged_change_log_delta_ids = vec![];
+        for (table_id, delta) in &self.change_log_delta {
+            if prev.change_log_delta.get(table_id) == Some(delta) {
+                unchanged_change_log_delta_ids.push(*table_id);
+            } else {
+                changed_change_log_delta.push((*table_id, delta));
+            }
+        }
+
+        let mut buf = vec![];
+        write_u64(&mut buf, self.max_committed_epoch);
+        write_u64(&mut buf, self.safe_epoch);
+        buf.push(self.trivial_move as u8);
+
+        write_u32(&mut buf, self.new_table_watermarks.len() as u32);
+        for (table_id, watermarks) in &self.new_table_watermarks {
+            write_u32(&mut buf, table_id.table_id);
+            write_message(&mut buf, &watermarks.to_protobuf());
+        }
+
+        write_u32(&mut buf, self.removed_table_ids.len() as u32);
+        for table_id in &self.removed_table_ids {
+            write_u32(&mut buf, table_id.table_id);
+        }
+
+        write_u32(&mut buf, changed_group_deltas.len() as u32);
+        for (cg_id, deltas) in changed_group_deltas {
+            write_u64(&mut buf, cg_id as u64);
+            write_message(&mut buf, deltas);
+        }
+        write_u32(&mut buf, unchanged_group_ids.len() as u32);
+        for cg_id in unchanged_group_ids {
+            write_u64(&mut buf, cg_id as u64);
+        }
+
+        write_u32(&mut buf, changed_state_table_info_delta.len() as u32);
+        for (table_id, delta) in changed_state_table_info_delta {
+            write_u32(&mut buf, table_id.table_id);
+            write_message(&mut buf, delta);
+        }
+        write_u32(&mut buf, unchanged_state_table_info_delta_ids.len() as u32);
+        for table_id in unchanged_state_table_info_delta_ids {
+            write_u32(&mut buf, table_id.table_id);
+        }
+
+        write_u32(&mut buf, changed_change_log_delta.len() as u32);
+        for (table_id, delta) in changed_change_log_delta {
+            write_u32(&mut buf, table_id.table_id);
+            write_message(&mut buf, delta);
+        }
+        write_u32(&mut buf, unchanged_change_log_delta_ids.len() as u32);
+        for table_id in unchanged_change_log_delta_ids {
+            write_u32(&mut buf, table_id.table_id);
+        }
+
+        let payload = zstd::stream::encode_all(&buf[..], COMPACT_DELTA_ZSTD_LEVEL)
+            .expect("in-memory zstd encoding of a compact version delta must not fail");
+
+        CompactVersionDeltaFrame {
+            prev_id: self.prev_id,
+            id: self.id,
+            payload,
+        }
+    }
+
+    /// Reconstructs the full [`IncompleteHummockVersionDelta`] that produced `frame`, given the
+    /// `prev` delta it was diffed against. Returns an error (rather than panicking) if
+    /// `prev.id != frame.prev_id`, since that means the receiver's state has diverged from the
+    /// sender's and it must request a full, uncompressed delta instead.
+    pub fn reconstruct(
+        prev: &IncompleteHummockVersionDelta,
+        frame: &CompactVersionDeltaFrame,
+    ) -> RefillResult<IncompleteHummockVersionDelta> {
+        if prev.id != frame.prev_id {
+            return Err(format!(
+                "compact version delta frame expects predecessor id {}, but the receiver's last delta is {}; a full delta must be requested instead",
+                frame.prev_id, prev.id
+            ));
+        }
+
+        let decoded = zstd::stream::decode_all(&frame.payload[..])
+            .map_err(|e| format!("failed to zstd-decode compact version delta payload: {e}"))?;
+        let mut reader = Reader::new(&decoded);
+
+        let max_committed_epoch = reader.read_u64()?;
+        let safe_epoch = reader.read_u64()?;
+        let trivial_move = reader.read_u8()? != 0;
+
+        let new_table_watermarks_len = reader.read_u32()?;
+        let mut new_table_watermarks = HashMap::with_capacity(new_table_watermarks_len as usize);
+        for _ in 0..new_table_watermarks_len {
+            let table_id = TableId::new(reader.read_u32()?);
+            let watermarks = reader.read_message()?;
+            new_table_watermarks.insert(table_id, TableWatermarks::from_protobuf(&watermarks));
+        }
+
+        let removed_table_ids_len = reader.read_u32()?;
+        let mut removed_table_ids = HashSet::with_capacity(removed_table_ids_len as usize);
+        for _ in 0..removed_table_ids_len {
+            removed_table_ids.insert(TableId::new(reader.read_u32()?));
+        }
+
+        let changed_group_deltas_len = reader.read_u32()?;
+        let mut group_deltas = HashMap::with_capacity(changed_group_deltas_len as usize);
+        for _ in 0..changed_group_deltas_len {
+            let cg_id = reader.read_u64()? as CompactionGroupId;
+            let deltas = reader.read_message()?;
+            group_deltas.insert(cg_id, deltas);
+        }
+        let unchanged_group_ids_len = reader.read_u32()?;
+        for _ in 0..unchanged_group_ids_len {
+            let cg_id = reader.read_u64()? as CompactionGroupId;
+            let deltas = prev.group_deltas.get(&cg_id).ok_or_else(|| {
+                format!("compact version delta references unchanged group {cg_id} absent from predecessor")
+            })?;
+            group_deltas.insert(cg_id, deltas.clone());
+        }
+
+        let changed_state_table_info_delta_len = reader.read_u32()?;
+        let mut state_table_info_delta =
+            HashMap::with_capacity(changed_state_table_info_delta_len as usize);
+        for _ in 0..changed_state_table_info_delta_len {
+            let table_id = TableId::new(reader.read_u32()?);
+            let delta = reader.read_message()?;
+            state_table_info_delta.insert(table_id, delta);
+        }
+        let unchanged_state_table_info_delta_ids_len = reader.read_u32()?;
+        for _ in 0..unchanged_state_table_info_delta_ids_len {
+            let table_id = TableId::new(reader.read_u32()?);
+            let delta = prev.state_table_info_delta.get(&table_id).ok_or_else(|| {
+                format!(
+                    "compact version delta references unchanged state table info {table_id:?} absent from predecessor"
+                )
+            })?;
+            state_table_info_delta.insert(table_id, delta.clone());
+        }
+
+        let changed_change_log_delta_len = reader.read_u32()?;
+        let mut change_log_delta = HashMap::with_capacity(changed_change_log_delta_len as usize);
+        for _ in 0..changed_change_log_delta_len {
+            let table_id = TableId::new(reader.read_u32()?);
+            let delta = reader.read_message()?;
+            change_log_delta.insert(table_id, delta);
+        }
+        let unchanged_change_log_delta_ids_len = reader.read_u32()?;
+        for _ in 0..unchanged_change_log_delta_ids_len {
+            let table_id = TableId::new(reader.read_u32()?);
+            let delta = prev.change_log_delta.get(&table_id).ok_or_else(|| {
+                format!(
+                    "compact version delta references unchanged change log {table_id:?} absent from predecessor"
+                )
+            })?;
+            change_log_delta.insert(table_id, delta.clone());
+        }
+
+        Ok(IncompleteHummockVersionDelta {
+            id: frame.id,
+            prev_id: frame.prev_id,
+            group_deltas,
+            max_committed_epoch,
+            safe_epoch,
+            trivial_move,
+            new_table_watermarks,
+            removed_table_ids,
+            change_log_delta,
+            state_table_info_delta,
+        })
+    }
+}